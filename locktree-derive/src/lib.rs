@@ -5,13 +5,17 @@ mod tests;
 
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use syn::{
-    braced, custom_keyword, parenthesized,
+    braced, custom_keyword,
+    ext::IdentExt,
+    parenthesized,
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
-    token::Paren,
-    AngleBracketedGenericArguments, Ident, Path, Token,
+    token::{Brace, Paren},
+    AngleBracketedGenericArguments, Attribute, Expr, GenericArgument,
+    GenericParam, Generics, Ident, Lifetime, LifetimeDef, Path, PathArguments,
+    Token, Type, Visibility, WhereClause,
 };
 
 struct LockTree {
@@ -20,86 +24,749 @@ struct LockTree {
 
 impl Parse for LockTree {
     fn parse(input: ParseStream) -> Result<Self> {
+        custom_keyword!(exclusive);
+
         let mut map = HashMap::new();
+        let mut names = HashSet::new();
+        let mut errors = Errors::default();
         while !input.is_empty() {
-            let name = input.parse::<Ident>()?;
-            let seq;
-            braced!(seq in input);
-            map.insert(name, seq.parse::<LockSequence>()?);
+            let header = (|| {
+                let attrs = input.call(Attribute::parse_outer)?;
+                let vis = input.parse::<Visibility>()?;
+                let is_exclusive = input.peek(exclusive);
+                if is_exclusive {
+                    input.parse::<exclusive>().unwrap();
+                }
+
+                let name = input.parse::<Ident>()?;
+                let mut generics = input.parse::<Generics>()?;
+                generics.where_clause = input.parse::<Option<WhereClause>>()?;
+                let seq;
+                braced!(seq in input);
+
+                Ok((attrs, vis, is_exclusive, name, generics, seq))
+            })();
+            let (attrs, vis, is_exclusive, name, generics, seq) = match header {
+                Ok(header) => header,
+                Err(error) => {
+                    errors.push(error);
+
+                    // Without a well-formed header there is no way to tell
+                    // where the next tree starts
+                    break;
+                }
+            };
+
+            check_attributes(&attrs, TREE_ATTRIBUTES, &mut errors);
+            let is_duplicate = !names.insert(name.unraw());
+            if is_duplicate {
+                errors.push(syn::Error::new(
+                    name.span(),
+                    format!("duplicate lock tree `{}`", name),
+                ));
+            }
+            for lifetime in generics.lifetimes() {
+                let lifetime = &lifetime.lifetime;
+                if RESERVED_LIFETIMES.iter().any(|x| lifetime.ident == x) {
+                    errors.push(syn::Error::new(
+                        lifetime.span(),
+                        format!(
+                            "`{}` is reserved for items generated by \
+                             `locktree!`",
+                            lifetime,
+                        ),
+                    ));
+                }
+            }
+
+            let poison = poison_policy(&attrs, &mut errors);
+            match seq.parse::<LockSequence>() {
+                Ok(mut seq) if !is_duplicate => {
+                    apply_poison_policy(
+                        &mut seq.seq,
+                        poison.map(|(_, x)| x),
+                        &mut errors,
+                    );
+                    check_construction(&attrs, &seq.seq, &mut errors);
+                    map.insert(
+                        name,
+                        LockSequence {
+                            attrs,
+                            vis,
+                            is_exclusive,
+                            generics,
+                            ..seq
+                        },
+                    );
+                }
+                Ok(_) => (),
+                Err(error) => errors.push(error),
+            }
         }
 
-        Ok(LockTree { map })
+        errors.finish(LockTree { map })
     }
 }
 
 struct LockSequence {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    is_exclusive: bool,
+    generics: Generics,
     seq: Vec<Lock>,
 }
 
 impl Parse for LockSequence {
     fn parse(input: ParseStream) -> Result<Self> {
-        Ok(Self {
-            seq: Punctuated::<Lock, Token![,]>::parse_terminated(input)?
-                .into_iter()
-                .collect(),
+        let mut errors = Errors::default();
+        let seq = parse_branch(input, &mut errors);
+
+        let mut valid = Vec::<(&Lock, String)>::new();
+        for flat_lock in flatten(&seq) {
+            let lock = flat_lock.lock;
+            check_attributes(&lock.attrs, LOCK_ATTRIBUTES, &mut errors);
+            // Locks under different `cfg` attributes may be alternatives that
+            // are never compiled together, so only locks under the same ones
+            // are checked against each other.
+            let cfg = flat_lock.cfg();
+            let others = valid
+                .iter()
+                .filter(|(_, other_cfg)| *other_cfg == cfg)
+                .map(|(other, _)| *other)
+                .collect::<Vec<_>>();
+            let name = lock.name.unraw();
+            let camel = snake_to_camel_case(&name.to_string());
+            if RESERVED_NAMES.iter().any(|x| name == x) {
+                errors.push(syn::Error::new(
+                    lock.name.span(),
+                    format!(
+                        "`{}` is reserved for items generated by `locktree!`",
+                        name,
+                    ),
+                ));
+            } else if camel.is_empty() {
+                errors.push(syn::Error::new(
+                    lock.name.span(),
+                    format!(
+                        "`{}` does not produce a forward struct name",
+                        lock.name,
+                    ),
+                ));
+            } else if others.iter().any(|x| x.name.unraw() == name) {
+                errors.push(syn::Error::new(
+                    lock.name.span(),
+                    format!("duplicate lock `{}`", name),
+                ));
+            } else if let Some(other) = others.iter().find(|x| {
+                // Each lock also has a forward struct with a `Deadline` suffix.
+                let other = snake_to_camel_case(&x.name.unraw().to_string());
+                other == camel
+                    || format!("{}Deadline", other) == camel
+                    || other == format!("{}Deadline", camel)
+            }) {
+                errors.push(syn::Error::new(
+                    lock.name.span(),
+                    format!(
+                        "`{}` and `{}` produce the same forward struct name",
+                        other.name, lock.name,
+                    ),
+                ));
+            } else if let Some((other, function)) =
+                others.iter().find_map(|x| {
+                    let names = x.function_names();
+                    lock.function_names()
+                        .into_iter()
+                        .find(|function| names.contains(function))
+                        .map(|function| (x, function))
+                })
+            {
+                errors.push(syn::Error::new(
+                    lock.name.span(),
+                    format!(
+                        "`{}` and `{}` both generate `{}`",
+                        other.name, lock.name, function,
+                    ),
+                ));
+            } else {
+                valid.push((lock, cfg));
+            }
+        }
+
+        errors.finish(Self {
+            attrs: Vec::new(),
+            vis: Visibility::Inherited,
+            is_exclusive: false,
+            generics: Generics::default(),
+            seq,
         })
     }
 }
 
+/// Parse a comma-separated sequence of locks. Parsing resumes after invalid
+/// locks so that all errors are reported at once.
+fn parse_branch(input: ParseStream, errors: &mut Errors) -> Vec<Lock> {
+    let mut seq = Vec::new();
+    while !input.is_empty() {
+        let lock = input.parse::<Lock>().and_then(|lock| {
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+
+            Ok(lock)
+        });
+        match lock {
+            Ok(lock) => seq.push(lock),
+            Err(error) => {
+                errors.push(error);
+                skip_past_comma(input);
+            }
+        }
+    }
+
+    seq
+}
+
+/// A lock along with its position in the tree.
+struct FlatLock<'a> {
+    /// Alternating positions in a sequence and branch indices, from the
+    /// top-level sequence down to the lock.
+    path: Vec<usize>,
+    /// `cfg` attributes of every ancestor, which also apply to this lock.
+    inherited_cfg_attrs: Vec<&'a Attribute>,
+    lock: &'a Lock,
+}
+
+impl FlatLock<'_> {
+    /// Whether `other` can be locked while holding this lock. Locks later in
+    /// the same sequence or inside of this lock's branches can, but locks in
+    /// a different branch of a common ancestor can't since they have no order
+    /// between them.
+    fn precedes(&self, other: &FlatLock) -> bool {
+        match self.path.iter().zip(&other.path).position(|(x, y)| x != y) {
+            Some(i) => i % 2 == 0 && self.path[i] < other.path[i],
+            None => self.path.len() < other.path.len(),
+        }
+    }
+
+    /// All `cfg` attributes applying to this lock.
+    fn cfg_attrs(&self) -> Vec<&Attribute> {
+        let cfg_attrs = select_attributes(&self.lock.attrs, &["cfg"]);
+
+        self.inherited_cfg_attrs
+            .iter()
+            .copied()
+            .chain(cfg_attrs)
+            .collect()
+    }
+
+    /// All `cfg` attributes applying to this lock, rendered so that they can
+    /// be compared.
+    fn cfg(&self) -> String {
+        self.cfg_attrs()
+            .iter()
+            .map(|x| x.to_token_stream().to_string())
+            .collect()
+    }
+
+    /// Predicate combining the `cfg` attributes applying to this lock, if
+    /// any.
+    fn cfg_predicate(&self) -> Option<TokenStream> {
+        let predicates = self
+            .cfg_attrs()
+            .iter()
+            .map(|x| x.parse_args::<TokenStream>().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        match predicates.as_slice() {
+            [] => None,
+            [predicate] => Some(predicate.clone()),
+            _ => Some(quote! {
+                all(#(#predicates),*)
+            }),
+        }
+    }
+}
+
+/// List all locks in `seq` and its branches in declaration order.
+fn flatten(seq: &[Lock]) -> Vec<FlatLock<'_>> {
+    fn flatten_into<'a>(
+        seq: &'a [Lock],
+        path: &[usize],
+        inherited_cfg_attrs: &[&'a Attribute],
+        output: &mut Vec<FlatLock<'a>>,
+    ) {
+        for (i, lock) in seq.iter().enumerate() {
+            let mut lock_path = path.to_vec();
+            lock_path.push(i);
+            let mut lock_cfg_attrs = inherited_cfg_attrs.to_vec();
+            lock_cfg_attrs.extend(select_attributes(&lock.attrs, &["cfg"]));
+            output.push(FlatLock {
+                path: lock_path.clone(),
+                inherited_cfg_attrs: inherited_cfg_attrs.to_vec(),
+                lock,
+            });
+
+            for (j, branch) in lock.branches.iter().enumerate() {
+                let mut branch_path = lock_path.clone();
+                branch_path.push(j);
+                flatten_into(branch, &branch_path, &lock_cfg_attrs, output);
+            }
+        }
+    }
+
+    let mut output = Vec::new();
+    flatten_into(seq, &[], &[], &mut output);
+
+    output
+}
+
+#[derive(Clone, Copy)]
+enum PoisonPolicy {
+    /// Panic when the lock is poisoned. This is the default.
+    Panic,
+    /// Ignore poisoning and return the guard anyway.
+    Recover,
+    /// Return a `LockResult` instead of the guard.
+    Propagate,
+}
+
+/// Find the `poison` attribute in `attrs`, if any.
+fn poison_policy<'a>(
+    attrs: &'a [Attribute],
+    errors: &mut Errors,
+) -> Option<(&'a Attribute, PoisonPolicy)> {
+    let mut policy = None;
+    for attr in attrs.iter().filter(|x| x.path.is_ident("poison")) {
+        if policy.is_some() {
+            errors.push(syn::Error::new_spanned(
+                attr,
+                "duplicate `poison` attribute",
+            ));
+            continue;
+        }
+
+        let value = match attr.parse_args::<Ident>() {
+            Ok(value) => value,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+        let value = if value == "panic" {
+            PoisonPolicy::Panic
+        } else if value == "recover" {
+            PoisonPolicy::Recover
+        } else if value == "propagate" {
+            PoisonPolicy::Propagate
+        } else {
+            errors.push(syn::Error::new(
+                value.span(),
+                "expected one of: `panic`, `recover`, `propagate`",
+            ));
+            continue;
+        };
+        policy = Some((attr, value));
+    }
+
+    policy
+}
+
+/// Set the poison policy of every lock in `seq` and its branches, either from
+/// its own `poison` attribute or from the tree's. Tree policies only apply to
+/// locks that use the standard library types.
+fn apply_poison_policy(
+    seq: &mut [Lock],
+    tree_policy: Option<PoisonPolicy>,
+    errors: &mut Errors,
+) {
+    for lock in seq {
+        match poison_policy(&lock.attrs, errors) {
+            Some((_, policy)) if lock.ty.has_default_type => {
+                lock.ty.set_poison_policy(policy)
+            }
+            Some((attr, _)) => errors.push(syn::Error::new_spanned(
+                attr,
+                "poison policies only apply to `Mutex` and `RwLock` locks \
+                 without an explicit HKT",
+            )),
+            None => {
+                if let Some(policy) = tree_policy {
+                    if lock.ty.has_default_type {
+                        lock.ty.set_poison_policy(policy);
+                    }
+                }
+            }
+        }
+        for branch in &mut lock.branches {
+            apply_poison_policy(branch, tree_policy, errors);
+        }
+    }
+}
+
+/// Whether `attrs` contain `#[builder]`, which opts into the generated builder.
+fn has_builder(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|x| x.path.is_ident("builder"))
+}
+
+/// Whether the tree implements `Default`, which is the case when it has locks
+/// and all of them have a default value.
+fn implements_default(seq: &[Lock]) -> bool {
+    let locks = flatten(seq);
+    !locks.is_empty() && locks.iter().all(|x| x.lock.default.is_some())
+}
+
+/// Check the `builder` attributes of a tree, that no lock clashes with the
+/// builder, that its default values are used by either the builder or
+/// `Default`, and that `Default` isn't also derived.
+fn check_construction(attrs: &[Attribute], seq: &[Lock], errors: &mut Errors) {
+    let mut builder = None;
+    for attr in attrs.iter().filter(|x| x.path.is_ident("builder")) {
+        if !attr.tokens.is_empty() {
+            errors.push(syn::Error::new_spanned(attr, "expected `#[builder]`"));
+        } else if builder.replace(attr).is_some() {
+            errors.push(syn::Error::new_spanned(
+                attr,
+                "duplicate `builder` attribute",
+            ));
+        }
+    }
+    if builder.is_some() {
+        for FlatLock { lock, .. } in flatten(seq) {
+            let name = lock.name.unraw();
+            if BUILDER_NAMES.iter().any(|x| name == x) {
+                errors.push(syn::Error::new(
+                    lock.name.span(),
+                    format!(
+                        "`{}` is reserved for items generated by `#[builder]`",
+                        name,
+                    ),
+                ));
+            }
+        }
+    }
+
+    if implements_default(seq) {
+        for attr in attrs.iter().filter(|x| x.path.is_ident("derive")) {
+            let paths = attr.parse_args_with(
+                Punctuated::<Path, Token![,]>::parse_terminated,
+            );
+            let default = paths.iter().flatten().find(|path| {
+                path.segments.last().is_some_and(|x| x.ident == "Default")
+            });
+            if let Some(path) = default {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "`Default` is already implemented by `locktree!` since \
+                     every lock has a default value",
+                ));
+            }
+        }
+    } else if builder.is_none() {
+        for FlatLock { lock, .. } in flatten(seq) {
+            if let Some(default) = &lock.default {
+                errors.push(syn::Error::new_spanned(
+                    default,
+                    "default values are only used by `#[builder]`, or by \
+                     `Default` when every lock has one",
+                ));
+            }
+        }
+    }
+}
+
+/// Lock names that would clash with the fields and functions generated by
+/// `locktree!`.
+const RESERVED_NAMES: &[&str] = &["deadline", "locks", "new", "token"];
+
+/// Lock names that would clash with the functions generated by `#[builder]`.
+const BUILDER_NAMES: &[&str] = &["build", "builder"];
+
+/// Attributes that can be written on a tree. `doc` and `derive` apply to the
+/// entry point, `poison` to the locks of the tree, `builder` generates a
+/// builder, and everything else applies to all generated items.
+const TREE_ATTRIBUTES: &[&str] =
+    &["allow", "builder", "cfg", "derive", "doc", "poison"];
+
+/// Attributes that can be written on a lock. `doc` applies to the lock's field
+/// and accessors, `cfg` to everything generated for that lock. `poison`
+/// overrides the poison policy of the tree.
+const LOCK_ATTRIBUTES: &[&str] = &["cfg", "doc", "poison"];
+
+/// Lifetime names (without the leading `'`) used in the generated accessors,
+/// forward structs and higher-ranked bounds.
+const RESERVED_LIFETIMES: &[&str] = &["a", "b", "x"];
+
 struct Lock {
-    name: String,
+    attrs: Vec<Attribute>,
+    name: Ident,
     ty: LockType,
+    /// Value used by `Default` and the builder when none is given.
+    default: Option<Expr>,
+    branches: Vec<Vec<Lock>>,
 }
 
 impl Lock {
-    fn fragment(&self, struct_prefix: &str) -> Fragment {
+    fn fragment(
+        &self,
+        struct_prefix: &str,
+        forward_args: &TokenStream,
+        is_exclusive: bool,
+        inherited_cfg_attrs: &[&Attribute],
+    ) -> Fragment {
         let forward = self.forward(struct_prefix);
-        let name =
-            proc_macro2::Ident::new(&self.name, proc_macro2::Span::call_site());
+        let name_str = self.name.unraw().to_string();
+        let name = field_ident(&name_str);
         let type_declaraction = self.ty.declaration();
         let init_var = proc_macro2::Ident::new(
-            &format!("{}_value", &self.name),
+            &format!("{}_value", &name_str),
             proc_macro2::Span::call_site(),
         );
         let generics = self.ty.generics();
+        let init_expression = self.ty.interface.init_expression(&init_var);
+        let cfg_attrs = select_attributes(&self.attrs, &["cfg"]);
+        let cfg_attrs = quote! {
+            #(#inherited_cfg_attrs)*
+            #(#cfg_attrs)*
+        };
+        let attrs = select_attributes(&self.attrs, &["cfg", "doc"]);
+        let lock_attrs = quote! {
+            #(#inherited_cfg_attrs)*
+            #(#attrs)*
+        };
+        // Generated functions start with their own documentation, so the
+        // documentation of the lock follows as a separate paragraph.
+        let doc_attrs =
+            select_attributes(&self.attrs, &["doc"]).collect::<Vec<_>>();
+        let separator = if doc_attrs.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! {
+                #[doc = ""]
+            }
+        };
+        let attrs = quote! {
+            #cfg_attrs
+            #separator
+            #(#doc_attrs)*
+        };
+        let missing_value = match &self.default {
+            Some(default) => default.to_token_stream(),
+            None => quote! {
+                return ::std::result::Result::Err(
+                    ::locktree::MissingValue::new(#name_str)
+                )
+            },
+        };
+
+        let accessors = self.ty.accessor_functions(
+            &name_str,
+            &forward,
+            forward_args,
+            is_exclusive,
+            &attrs,
+        );
+        let mut main_accessors = accessors.entry;
+        if self.ty.poison.is_some() {
+            let clear_poison_fn = proc_macro2::Ident::new(
+                &format!("clear_poison_{}", name_str),
+                proc_macro2::Span::call_site(),
+            );
+            let doc = format!("Clears the poison of `{}`.", name_str);
+            main_accessors.extend(quote! {
+                #[doc = #doc]
+                #attrs
+                pub fn #clear_poison_fn(&self) {
+                    ::locktree::ClearPoison::clear_poison(&self.#name)
+                }
+            });
+        }
+        main_accessors.extend(self.ty.map_functions(
+            &name_str,
+            AccessorKind::Entry,
+            &attrs,
+        ));
 
         Fragment {
-            main_accessors: self
+            main_accessors,
+            forward_accessors: accessors.forward,
+            forward_into_accessors: accessors.forward_into,
+            deadline_accessors: accessors.deadline_entry,
+            deadline_forward_accessors: accessors.deadline_forward,
+            deadline_forward_into_accessors: accessors.deadline_forward_into,
+            out_of_order_accessors: self
+                .ty
+                .out_of_order_functions(&name_str, &attrs),
+            conversion_functions: self
                 .ty
-                .accessor_functions(&self.name, &forward, true),
-            forward_accessors: self
+                .conversion_functions(&name_str, false, &attrs),
+            deadline_conversion_functions: self
                 .ty
-                .accessor_functions(&self.name, &forward, false),
+                .conversion_functions(&name_str, true, &attrs),
+            shared_accessor: self.ty.shared_function(&name_str, &attrs),
             forward,
             lock_declaration: quote! {
+                #lock_attrs
                 #name: #type_declaraction,
             },
             init_arg: quote! {
+                #cfg_attrs
                 #init_var: #generics
             },
             init_statement: quote! {
-                #name: ::locktree::New::new(#init_var),
+                #cfg_attrs
+                #name: #init_expression,
+            },
+            default_statement: self.default.as_ref().map(|default| {
+                quote! {
+                    #cfg_attrs
+                    let #init_var: #generics = #default;
+                }
+            }),
+            builder_field: quote! {
+                #cfg_attrs
+                #name: ::std::option::Option<#generics>,
+            },
+            builder_init: quote! {
+                #cfg_attrs
+                #name: ::std::option::Option::None,
+            },
+            builder_setter: {
+                let doc = format!("Sets the initial value of `{}`.", name_str);
+
+                quote! {
+                    #[doc = #doc]
+                    #attrs
+                    pub fn #name(mut self, value: #generics) -> Self {
+                        self.#name = ::std::option::Option::Some(value);
+                        self
+                    }
+                }
+            },
+            builder_statement: quote! {
+                #cfg_attrs
+                let #init_var = match self.#name {
+                    ::std::option::Option::Some(value) => value,
+                    ::std::option::Option::None => #missing_value,
+                };
             },
+            cfg_attrs,
+        }
+    }
+
+    /// Names of the functions generated for this lock, on the entry point or
+    /// on forward locktrees.
+    fn function_names(&self) -> Vec<String> {
+        let name = self.name.unraw();
+        let methods: &[_] = match self.ty.interface {
+            LockInterface::Mutex => &["lock"],
+            LockInterface::RwLock => &["read", "write"],
+            LockInterface::UpgradableRwLock => &["read", "write"],
+            LockInterface::Tree => {
+                return vec![
+                    name.to_string(),
+                    format!("enter_{}", name),
+                    format!("into_enter_{}", name),
+                ]
+            }
+            LockInterface::Shared => return vec![name.to_string()],
+        };
+
+        let mut accessors = Vec::new();
+        for method in methods {
+            accessors.push(format!("{}_{}", method, name));
+            accessors.push(format!("try_{}_{}", method, name));
+            for suffix in ["for", "until", "with_deadline"] {
+                accessors.push(format!("{}_{}_{}", method, name, suffix));
+            }
+        }
+        if let LockInterface::UpgradableRwLock = self.ty.interface {
+            accessors.push(format!("upgradable_read_{}", name));
+            accessors.push(format!("try_upgradable_read_{}", name));
         }
+
+        let mut names = accessors.clone();
+        names.extend(accessors.iter().map(|x| format!("into_{}", x)));
+        for method in methods {
+            names.push(format!("try_{}_{}_out_of_order", method, name));
+        }
+        names.push(format!("clear_poison_{}", name));
+        let map_methods: &[_] = match self.ty.interface {
+            LockInterface::Mutex => &["map"],
+            _ => &["map_read", "map_write"],
+        };
+        for method in map_methods {
+            names.push(format!("{}_{}", method, name));
+            names.push(format!("try_{}_{}", method, name));
+        }
+        if let LockInterface::RwLock | LockInterface::UpgradableRwLock =
+            self.ty.interface
+        {
+            names.push(format!("downgrade_{}", name));
+        }
+        if let LockInterface::UpgradableRwLock = self.ty.interface {
+            names.push(format!("upgrade_{}", name));
+        }
+
+        names
     }
 
     fn forward(&self, struct_prefix: &str) -> String {
-        format!("{}{}", struct_prefix, snake_to_camel_case(&self.name))
+        format!(
+            "{}{}",
+            struct_prefix,
+            snake_to_camel_case(&self.name.unraw().to_string())
+        )
     }
 }
 
 impl Parse for Lock {
     fn parse(input: ParseStream) -> Result<Self> {
-        let name = input.parse::<Ident>()?.to_string();
+        let attrs = input.call(Attribute::parse_outer)?;
+        let name = input.parse::<Ident>()?;
         input.parse::<Token![:]>()?;
         let ty = input.parse::<LockType>()?;
+        // Branches follow the default value, so struct literals must be
+        // parenthesized, like in `if` conditions. The parentheses are dropped
+        // to avoid `unused_parens` warnings in the generated code.
+        let default = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            match Expr::parse_without_eager_brace(input)? {
+                Expr::Paren(expr) => Some(*expr.expr),
+                expr => Some(expr),
+            }
+        } else {
+            None
+        };
+        let mut branches = Vec::new();
+        let mut errors = Errors::default();
+        while input.peek(Brace) {
+            let branch;
+            let brace = braced!(branch in input);
+            if let LockInterface::Shared = ty.interface {
+                errors.push(syn::Error::new(
+                    brace.span,
+                    "shared fields can't have branches",
+                ));
+            }
+            branches.push(parse_branch(&branch, &mut errors));
+        }
 
-        Ok(Self { name, ty })
+        errors.finish(Self {
+            attrs,
+            name,
+            ty,
+            default,
+            branches,
+        })
     }
 }
 
 struct LockType {
     is_async: bool,
+    /// Whether this is a `Mutex` or `RwLock` from the standard library.
+    has_default_type: bool,
+    /// Poison policy, if any was given for this lock or its tree.
+    poison: Option<PoisonPolicy>,
     declaration: TokenStream,
     generics: TokenStream,
     interface: LockInterface,
@@ -110,32 +777,218 @@ impl LockType {
         &self,
         name: &str,
         forward: &str,
-        is_entry_point: bool,
-    ) -> TokenStream {
-        let name =
-            proc_macro2::Ident::new(&name, proc_macro2::Span::call_site());
+        forward_args: &TokenStream,
+        is_exclusive: bool,
+        attrs: &TokenStream,
+    ) -> Accessors {
+        let name = field_ident(name);
+        let deadline_forward = proc_macro2::Ident::new(
+            &format!("{}Deadline", forward),
+            proc_macro2::Span::call_site(),
+        );
         let forward =
-            proc_macro2::Ident::new(&forward, proc_macro2::Span::call_site());
-        let accessor = if is_entry_point {
-            quote! {
-                self
-            }
-        } else {
-            quote! {
-                self.locks
-            }
+            proc_macro2::Ident::new(forward, proc_macro2::Span::call_site());
+        let functions = |kind: AccessorKind, has_deadline: bool| {
+            let (fn_prefix, fn_generics, lifetime, receiver, accessor) =
+                match kind {
+                    AccessorKind::Entry => (
+                        "",
+                        quote! {
+                            <'a>
+                        },
+                        quote! {
+                            'a
+                        },
+                        quote! {
+                            &'a self
+                        },
+                        if has_deadline {
+                            quote! {
+                                self.locks
+                            }
+                        } else {
+                            quote! {
+                                self
+                            }
+                        },
+                    ),
+                    AccessorKind::Forward => (
+                        "",
+                        quote! {
+                            <'a>
+                        },
+                        quote! {
+                            'a
+                        },
+                        quote! {
+                            &'a mut self
+                        },
+                        quote! {
+                            self.locks
+                        },
+                    ),
+                    AccessorKind::ForwardInto => (
+                        "into_",
+                        TokenStream::new(),
+                        quote! {
+                            'b
+                        },
+                        quote! {
+                            self
+                        },
+                        quote! {
+                            self.locks
+                        },
+                    ),
+                };
+            let (receiver, forward_fields) = match (is_exclusive, kind) {
+                (false, _) => (
+                    receiver,
+                    quote! {
+                        locks: #accessor
+                    },
+                ),
+                (true, AccessorKind::Entry) => (
+                    quote! {
+                        #receiver,
+                        token: &'a mut ::locktree::LockToken
+                    },
+                    quote! {
+                        locks: #accessor, token
+                    },
+                ),
+                (true, AccessorKind::Forward) => (
+                    receiver,
+                    quote! {
+                        locks: #accessor, token: &mut *self.token
+                    },
+                ),
+                (true, AccessorKind::ForwardInto) => (
+                    receiver,
+                    quote! {
+                        locks: #accessor, token: self.token
+                    },
+                ),
+            };
+            let deadline_forward_type = quote! {
+                #deadline_forward<#lifetime #forward_args>
+            };
+
+            self.interface.accessor_functions(
+                self.is_async,
+                &name,
+                &self.declaration,
+                &AccessorContext {
+                    kind,
+                    fn_prefix,
+                    fn_generics,
+                    forward: if has_deadline {
+                        deadline_forward.clone()
+                    } else {
+                        forward.clone()
+                    },
+                    forward_type: if has_deadline {
+                        deadline_forward_type.clone()
+                    } else {
+                        quote! {
+                            #forward<#lifetime #forward_args>
+                        }
+                    },
+                    deadline_forward: deadline_forward.clone(),
+                    deadline_forward_type,
+                    lifetime,
+                    receiver,
+                    accessor,
+                    forward_fields,
+                    deadline: has_deadline.then(|| {
+                        quote! {
+                            self.deadline
+                        }
+                    }),
+                    attrs,
+                    is_async: self.is_async,
+                },
+            )
         };
 
-        self.interface.accessor_functions(
-            !is_entry_point,
+        Accessors {
+            entry: functions(AccessorKind::Entry, false),
+            forward: functions(AccessorKind::Forward, false),
+            forward_into: functions(AccessorKind::ForwardInto, false),
+            deadline_entry: functions(AccessorKind::Entry, true),
+            deadline_forward: functions(AccessorKind::Forward, true),
+            deadline_forward_into: functions(AccessorKind::ForwardInto, true),
+        }
+    }
+
+    fn out_of_order_functions(
+        &self,
+        name: &str,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        let name = field_ident(name);
+
+        self.interface.out_of_order_functions(
+            self.is_async,
+            &name,
+            &self.declaration,
+            attrs,
+        )
+    }
+
+    fn shared_function(&self, name: &str, attrs: &TokenStream) -> TokenStream {
+        let name = field_ident(name);
+
+        self.interface
+            .shared_function(&name, &self.declaration, attrs)
+    }
+
+    fn conversion_functions(
+        &self,
+        name: &str,
+        has_deadline: bool,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        let ident = field_ident(name);
+        let mut code = self.interface.conversion_functions(
+            self.is_async,
+            &ident,
+            &self.declaration,
+            has_deadline,
+            attrs,
+        );
+        code.extend(self.map_functions(name, AccessorKind::Forward, attrs));
+
+        code
+    }
+
+    fn map_functions(
+        &self,
+        name: &str,
+        kind: AccessorKind,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        let name = field_ident(name);
+
+        self.interface.map_functions(
             self.is_async,
             &name,
-            &forward,
-            &accessor,
             &self.declaration,
+            kind,
+            attrs,
         )
     }
 
+    /// Switch to the standard library lock type for `poison`.
+    fn set_poison_policy(&mut self, poison: PoisonPolicy) {
+        let hkt = self.interface.default_concrete_type(poison);
+        let generics = &self.generics;
+        self.declaration = quote! {
+            #hkt<#generics>
+        };
+        self.poison = Some(poison);
+    }
+
     fn declaration(&self) -> &TokenStream {
         &self.declaration
     }
@@ -152,12 +1005,56 @@ impl Parse for LockType {
             input.parse::<Token![async]>().unwrap();
         }
 
+        let interface_span = input.span();
         let interface = input.parse::<LockInterface>()?;
-        let hkt = if input.peek(Paren) {
+        if let LockInterface::Tree | LockInterface::Shared = interface {
+            let (kind, expected) = match interface {
+                LockInterface::Tree => {
+                    ("nested trees", "a single lock tree type")
+                }
+                _ => ("shared fields", "a single type"),
+            };
+            if is_async || input.peek(Paren) {
+                return Err(syn::Error::new(
+                    interface_span,
+                    format!("{} can't be async or have an explicit HKT", kind),
+                ));
+            }
+
+            let ty = input.parse::<AngleBracketedGenericArguments>()?.args;
+            if ty.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!("expected {}", expected),
+                ));
+            }
+            let ty = ty.into_token_stream();
+
+            return Ok(Self {
+                is_async,
+                has_default_type: false,
+                poison: None,
+                declaration: ty.clone(),
+                generics: ty,
+                interface,
+            });
+        }
+
+        let has_explicit_hkt = input.peek(Paren);
+        let hkt = if has_explicit_hkt {
             let hkt;
             parenthesized!(hkt in input);
+            let path = hkt.parse::<Path>()?;
+            if !hkt.is_empty() {
+                return Err(hkt.error("unexpected token"));
+            }
 
-            hkt.parse::<Path>()?.into_token_stream()
+            match path.get_ident() {
+                Some(shorthand) => interface
+                    .shorthand_type(is_async, &shorthand.to_string())
+                    .unwrap_or_else(|| path.into_token_stream()),
+                None => path.into_token_stream(),
+            }
         } else {
             if is_async {
                 return Err(syn::Error::new(
@@ -165,8 +1062,14 @@ impl Parse for LockType {
                     "async locks must have an explicit HKT",
                 ));
             }
+            if let LockInterface::UpgradableRwLock = interface {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`UpgradableRwLock` locks must have an explicit HKT",
+                ));
+            }
 
-            interface.default_concrete_type()
+            interface.default_concrete_type(PoisonPolicy::Panic)
         };
         let generics = input
             .parse::<AngleBracketedGenericArguments>()?
@@ -175,6 +1078,8 @@ impl Parse for LockType {
 
         Ok(Self {
             is_async,
+            has_default_type: !is_async && !has_explicit_hkt,
+            poison: None,
             declaration: quote! {
                 #hkt<#generics>
             },
@@ -188,115 +1093,1109 @@ impl Parse for LockType {
 enum LockInterface {
     Mutex,
     RwLock,
+    UpgradableRwLock,
+    Tree,
+    Shared,
 }
 
 impl LockInterface {
-    fn default_concrete_type(&self) -> TokenStream {
-        match self {
-            Self::Mutex => quote! {
+    fn default_concrete_type(&self, poison: PoisonPolicy) -> TokenStream {
+        match (self, poison) {
+            (Self::Mutex, PoisonPolicy::Panic) => quote! {
                 ::std::sync::Mutex
             },
-            Self::RwLock => quote! {
+            (Self::RwLock, PoisonPolicy::Panic) => quote! {
                 ::std::sync::RwLock
             },
+            (Self::Mutex, PoisonPolicy::Recover) => quote! {
+                ::locktree::poison::RecoverMutex
+            },
+            (Self::RwLock, PoisonPolicy::Recover) => quote! {
+                ::locktree::poison::RecoverRwLock
+            },
+            (Self::Mutex, PoisonPolicy::Propagate) => quote! {
+                ::locktree::poison::PropagateMutex
+            },
+            (Self::RwLock, PoisonPolicy::Propagate) => quote! {
+                ::locktree::poison::PropagateRwLock
+            },
+            (Self::UpgradableRwLock | Self::Tree | Self::Shared, _) => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Lock type for a shorthand HKT such as `Mutex(parking_lot)`.
+    fn shorthand_type(
+        &self,
+        is_async: bool,
+        shorthand: &str,
+    ) -> Option<TokenStream> {
+        match (self, is_async, shorthand) {
+            (Self::Mutex, false, "parking_lot") => Some(quote! {
+                ::locktree::parking_lot::Mutex
+            }),
+            (Self::Mutex, false, "parking_lot_fair") => Some(quote! {
+                ::locktree::parking_lot::FairMutex
+            }),
+            (Self::Mutex, false, "parking_lot_reentrant") => Some(quote! {
+                ::locktree::parking_lot::ReentrantMutex
+            }),
+            (Self::RwLock | Self::UpgradableRwLock, false, "parking_lot") => {
+                Some(quote! {
+                    ::locktree::parking_lot::RwLock
+                })
+            }
+            (Self::Mutex, true, "async_lock") => Some(quote! {
+                ::locktree::async_lock::Mutex
+            }),
+            (Self::RwLock | Self::UpgradableRwLock, true, "async_lock") => {
+                Some(quote! {
+                    ::locktree::async_lock::RwLock
+                })
+            }
+            // `async-std` re-exports the locks of `async-lock`, so they share
+            // its trait implementations.
+            (Self::Mutex, true, "async_std") => Some(quote! {
+                ::locktree::async_std::sync::Mutex
+            }),
+            (Self::RwLock | Self::UpgradableRwLock, true, "async_std") => {
+                Some(quote! {
+                    ::locktree::async_std::sync::RwLock
+                })
+            }
+            (Self::Mutex, true, "futures") => Some(quote! {
+                ::locktree::futures::lock::Mutex
+            }),
+            _ => None,
+        }
+    }
+
+    fn init_expression(&self, init_var: &proc_macro2::Ident) -> TokenStream {
+        match self {
+            Self::Mutex | Self::RwLock | Self::UpgradableRwLock => quote! {
+                ::locktree::New::new(#init_var)
+            },
+            Self::Tree | Self::Shared => quote! {
+                #init_var
+            },
         }
     }
 
     fn accessor_functions(
         &self,
-        use_mut_ref: bool,
         is_async: bool,
         name: &proc_macro2::Ident,
-        forward: &proc_macro2::Ident,
-        accessor: &TokenStream,
         declaration: &TokenStream,
+        context: &AccessorContext,
     ) -> TokenStream {
-        let mut_keyword = if use_mut_ref {
-            Some(proc_macro2::Ident::new(
-                "mut",
-                proc_macro2::Span::call_site(),
-            ))
-        } else {
-            None
-        };
-        match self {
-            Self::Mutex => {
-                let lock_fn_name = proc_macro2::Ident::new(
-                    &format!("lock_{}", name),
-                    proc_macro2::Span::call_site(),
-                );
-                let async_keyword = if is_async { "Async" } else { "" };
-                let guard = proc_macro2::Ident::new(
-                    &format!("Plugged{}MutexGuard", async_keyword),
-                    proc_macro2::Span::call_site(),
-                );
-                let lock = proc_macro2::Ident::new(
-                    &format!("{}Mutex", async_keyword),
-                    proc_macro2::Span::call_site(),
-                );
-
-                quote! {
-                    pub fn #lock_fn_name<'a>(
-                        &'a #mut_keyword self
-                    ) -> (
-                        ::locktree::#guard<'a, #declaration>,
-                        #forward<'a>
-                    ) {
-                        (::locktree::#lock::lock(&#accessor.#name), #forward { locks: #accessor })
+        let AccessorContext {
+            lifetime, accessor, ..
+        } = context;
+        let async_keyword = if is_async { "Async" } else { "" };
+        let (lock, methods): (_, &[_]) = match self {
+            Self::Mutex => ("Mutex", &[("lock", "Guard")]),
+            Self::RwLock | Self::UpgradableRwLock => {
+                ("RwLock", &[("read", "ReadGuard"), ("write", "WriteGuard")])
+            }
+            Self::Shared => {
+                return match context.kind {
+                    AccessorKind::Entry => {
+                        let attrs = context.attrs;
+                        let doc = format!("Shared field `{}`.", name.unraw());
+                        quote! {
+                            #[doc = #doc]
+                            #attrs
+                            pub fn #name(&self) -> &#declaration {
+                                &#accessor.#name
+                            }
+                        }
+                    }
+                    AccessorKind::Forward | AccessorKind::ForwardInto => {
+                        TokenStream::new()
                     }
                 }
             }
-            Self::RwLock => {
-                let read_fn_name = proc_macro2::Ident::new(
-                    &format!("read_{}", name),
-                    proc_macro2::Span::call_site(),
-                );
-                let write_fn_name = proc_macro2::Ident::new(
-                    &format!("write_{}", name),
-                    proc_macro2::Span::call_site(),
+            Self::Tree => {
+                return context.enter_function(
+                    &format!("enter_{}", name.unraw()),
+                    &format!(
+                        "Enters the nested tree `{}`. It is then reached \
+                         through the returned forward locktree.",
+                        name.unraw()
+                    ),
+                )
+            }
+        };
+        // Timed locking is optional for `async` locks. As for mapping, the
+        // bound is higher-ranked so that it is checked when the accessor is
+        // called rather than where the locktree is declared.
+        let (timed_lock, timed_bound) = if is_async {
+            let timed_lock = proc_macro2::Ident::new(
+                &format!("AsyncTimed{}", lock),
+                proc_macro2::Span::call_site(),
+            );
+            let bound = quote! {
+                where
+                    for<'x> #declaration: ::locktree::#timed_lock,
+            };
+            (timed_lock, bound)
+        } else {
+            let timed_lock =
+                proc_macro2::Ident::new(lock, proc_macro2::Span::call_site());
+            (timed_lock, TokenStream::new())
+        };
+        let lock = proc_macro2::Ident::new(
+            &format!("{}{}", async_keyword, lock),
+            proc_macro2::Span::call_site(),
+        );
+        let method = |prefix: &str, method: &str, suffix: &str| {
+            proc_macro2::Ident::new(
+                &format!("{}{}{}", prefix, method, suffix),
+                proc_macro2::Span::call_site(),
+            )
+        };
+        let guard = |assoc: &str| {
+            guard_type(is_async, &lock, assoc, declaration, lifetime)
+        };
+        // Accessors waiting for the lock return a future resolving to
+        // `output` for `async` locks.
+        let future = |output: TokenStream| {
+            quote! {
+                impl ::std::future::Future<Output = #output> + #lifetime
+            }
+        };
+
+        let action = |method: &str| {
+            format!("Locks `{}`{}", name.unraw(), lock_purpose(method))
+        };
+        let function_doc = |method: &str| {
+            format!(
+                "{}. Returns its guard along with a forward locktree to take \
+                 the next locks.",
+                action(method)
+            )
+        };
+        let try_doc = |method: &str| {
+            format!(
+                "Tries to lock `{}`{} without waiting.",
+                name.unraw(),
+                lock_purpose(method)
+            )
+        };
+
+        // Accessors of an entry point or forward locktree with a deadline all
+        // wait until that deadline, so they have no other timed variants.
+        let has_deadline = context.deadline.is_some();
+        let deadline_doc = |method: &str| {
+            format!(
+                "{}, giving up at the deadline. Returns its guard along with \
+                 a forward locktree keeping that deadline.",
+                action(method)
+            )
+        };
+        let deadline_guard = |guard: TokenStream| {
+            if is_async {
+                future(quote! {
+                    ::std::result::Result<#guard, ::locktree::DeadlineExceeded>
+                })
+            } else {
+                guard
+            }
+        };
+        let lock_name = name.unraw().to_string();
+
+        let mut code = TokenStream::new();
+        for (name_prefix, assoc) in methods {
+            let fn_name = format!("{}_{}", name_prefix, name.unraw());
+            let guard = guard(assoc);
+            code.extend(if has_deadline {
+                let lock_until_fn = method("", name_prefix, "_until");
+                context.deadline_function(
+                    &fn_name,
+                    &deadline_doc(name_prefix),
+                    &lock_name,
+                    deadline_guard(guard),
+                    quote! {
+                        ::locktree::#timed_lock::#lock_until_fn(&#accessor.#name, deadline)
+                    },
+                    timed_bound.clone(),
+                )
+            } else {
+                let lock_fn = method("", name_prefix, "");
+                context.function(
+                    &fn_name,
+                    &function_doc(name_prefix),
+                    TokenStream::new(),
+                    if is_async { future(guard) } else { guard },
+                    quote! {
+                        ::locktree::#lock::#lock_fn(&#accessor.#name)
+                    },
+                    TokenStream::new(),
+                )
+            });
+        }
+        for (name_prefix, assoc) in methods {
+            let lock_fn = method("try_", name_prefix, "");
+            code.extend(context.try_function(
+                &format!("try_{}_{}", name_prefix, name.unraw()),
+                &try_doc(name_prefix),
+                guard(assoc),
+                quote! {
+                    ::locktree::#lock::#lock_fn(&#accessor.#name)
+                },
+            ));
+        }
+        if !has_deadline {
+            for (suffix, arg, arg_ty, limit) in [
+                (
+                    "_for",
+                    quote! { timeout },
+                    quote! { ::std::time::Duration },
+                    "after `timeout`",
+                ),
+                (
+                    "_until",
+                    quote! { deadline },
+                    quote! { ::std::time::Instant },
+                    "at `deadline`",
+                ),
+            ] {
+                for (name_prefix, assoc) in methods {
+                    let fn_name =
+                        format!("{}_{}{}", name_prefix, name.unraw(), suffix);
+                    let args = quote! {
+                        , #arg: #arg_ty
+                    };
+                    let lock_fn = method("", name_prefix, suffix);
+                    let lock_call = quote! {
+                        ::locktree::#timed_lock::#lock_fn(&#accessor.#name, #arg)
+                    };
+                    let doc = format!(
+                        "{}, giving up {}.",
+                        action(name_prefix),
+                        limit
+                    );
+                    let guard = guard(assoc);
+                    code.extend(if is_async {
+                        context.function(
+                            &fn_name,
+                            &doc,
+                            args,
+                            future(quote! {
+                                ::std::result::Result<#guard, ::locktree::TimedOut>
+                            }),
+                            lock_call,
+                            timed_bound.clone(),
+                        )
+                    } else {
+                        context
+                            .timed_function(&fn_name, &doc, args, guard, lock_call)
+                    });
+                }
+            }
+        }
+        if let (AccessorKind::Entry, false) = (context.kind, has_deadline) {
+            for (name_prefix, assoc) in methods {
+                let fn_name =
+                    format!("{}_{}_with_deadline", name_prefix, name.unraw());
+                let doc = format!(
+                    "{}, giving up at `deadline`. The returned forward \
+                     locktree keeps that deadline.",
+                    action(name_prefix)
                 );
-                let async_keyword = if is_async { "Async" } else { "" };
-                let read_guard = proc_macro2::Ident::new(
-                    &format!("Plugged{}RwLockReadGuard", async_keyword),
-                    proc_macro2::Span::call_site(),
+                let lock_until_fn = method("", name_prefix, "_until");
+                code.extend(context.deadline_function(
+                    &fn_name,
+                    &doc,
+                    &lock_name,
+                    deadline_guard(guard(assoc)),
+                    quote! {
+                        ::locktree::#timed_lock::#lock_until_fn(&#accessor.#name, deadline)
+                    },
+                    timed_bound.clone(),
+                ));
+            }
+        }
+        if let Self::UpgradableRwLock = self {
+            let lock = proc_macro2::Ident::new(
+                &format!("{}UpgradableRwLock", async_keyword),
+                proc_macro2::Span::call_site(),
+            );
+            let guard = guard_type(
+                is_async,
+                &lock,
+                "UpgradableGuard",
+                declaration,
+                lifetime,
+            );
+            let fn_name = format!("upgradable_read_{}", name.unraw());
+            // `async` locks can't give up waiting for an upgradable read, so
+            // it can only be tried with a deadline.
+            if !has_deadline {
+                code.extend(context.function(
+                    &fn_name,
+                    &function_doc("upgradable_read"),
+                    TokenStream::new(),
+                    if is_async {
+                        future(guard.clone())
+                    } else {
+                        guard.clone()
+                    },
+                    quote! {
+                        ::locktree::#lock::upgradable_read(&#accessor.#name)
+                    },
+                    TokenStream::new(),
+                ));
+            } else if !is_async {
+                code.extend(context.deadline_function(
+                    &fn_name,
+                    &deadline_doc("upgradable_read"),
+                    &lock_name,
+                    guard.clone(),
+                    quote! {
+                        ::locktree::#lock::upgradable_read_until(&#accessor.#name, deadline)
+                    },
+                    TokenStream::new(),
+                ));
+            }
+            code.extend(context.try_function(
+                &format!("try_upgradable_read_{}", name.unraw()),
+                &try_doc("upgradable_read"),
+                guard,
+                quote! {
+                    ::locktree::#lock::try_upgradable_read(&#accessor.#name)
+                },
+            ));
+        }
+
+        code
+    }
+
+    /// Functions of the lock's own forward locktree converting the guard
+    /// returned along with it, or giving access to a nested tree. Upgrades and
+    /// nested trees borrow the forward, which guarantees that no lock coming
+    /// after this one is held while waiting for them.
+    fn conversion_functions(
+        &self,
+        is_async: bool,
+        name: &proc_macro2::Ident,
+        declaration: &TokenStream,
+        has_deadline: bool,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        let async_keyword = if is_async { "Async" } else { "" };
+        let lock = |trait_name: &str| {
+            proc_macro2::Ident::new(
+                &format!("{}{}", async_keyword, trait_name),
+                proc_macro2::Span::call_site(),
+            )
+        };
+        let fn_name = |method: &str| {
+            proc_macro2::Ident::new(
+                &format!("{}_{}", method, name.unraw()),
+                proc_macro2::Span::call_site(),
+            )
+        };
+        let b = quote! {
+            'b
+        };
+
+        let mut code = TokenStream::new();
+        // With a deadline, the nested tree is reached through its entry point
+        // with the same deadline. The bound is higher-ranked like for
+        // downgrades, for nested trees that aren't generated by `locktree!`.
+        if let Self::Tree = self {
+            code.extend(if has_deadline {
+                let doc = format!(
+                    "Nested tree `{}`, giving up at the deadline of this \
+                     forward locktree. Its guards keep this forward locktree \
+                     borrowed.",
+                    name.unraw()
                 );
-                let write_guard = proc_macro2::Ident::new(
-                    &format!("Plugged{}RwLockWriteGuard", async_keyword),
-                    proc_macro2::Span::call_site(),
+
+                quote! {
+                    #[doc = #doc]
+                    #attrs
+                    pub fn #name(
+                        &mut self
+                    ) -> <#declaration as ::locktree::WithDeadline>::Deadline<'_>
+                    where
+                        for<'x> #declaration: ::locktree::WithDeadline,
+                    {
+                        ::locktree::WithDeadline::with_deadline(
+                            &self.locks.#name,
+                            self.deadline,
+                        )
+                    }
+                }
+            } else {
+                let doc = format!(
+                    "Nested tree `{}`. Its guards keep this forward locktree \
+                     borrowed.",
+                    name.unraw()
                 );
-                let lock = proc_macro2::Ident::new(
-                    &format!("{}RwLock", async_keyword),
+
+                quote! {
+                    #[doc = #doc]
+                    #attrs
+                    pub fn #name(&mut self) -> &#declaration {
+                        &self.locks.#name
+                    }
+                }
+            });
+        }
+        if let Self::RwLock | Self::UpgradableRwLock = self {
+            let rw_lock = lock("RwLock");
+            let read_guard =
+                guard_type(is_async, &rw_lock, "ReadGuard", declaration, &b);
+            let write_guard =
+                guard_type(is_async, &rw_lock, "WriteGuard", declaration, &b);
+            let lock = lock("DowngradableRwLock");
+            let fn_name = fn_name("downgrade");
+            let doc = format!(
+                "Downgrades a write guard of `{}` to a read guard.",
+                name.unraw()
+            );
+            // Only usable if the lock implements the trait. The bound is
+            // higher-ranked so that it isn't rejected as trivially false for
+            // locks that don't.
+            code.extend(quote! {
+                #[doc = #doc]
+                #attrs
+                pub fn #fn_name(
+                    &self,
+                    guard: #write_guard,
+                ) -> #read_guard
+                where
+                    for<'x> #declaration: ::locktree::#lock,
+                {
+                    <#declaration as ::locktree::#lock>::downgrade(guard)
+                }
+            });
+        }
+        // Upgrades can't give up waiting, so forward locktrees with a deadline
+        // don't have them.
+        if let (Self::UpgradableRwLock, false) = (self, has_deadline) {
+            let write_guard = guard_type(
+                is_async,
+                &lock("RwLock"),
+                "WriteGuard",
+                declaration,
+                &b,
+            );
+            let lock = lock("UpgradableRwLock");
+            let upgradable_guard =
+                guard_type(is_async, &lock, "UpgradableGuard", declaration, &b);
+            let fn_name = fn_name("upgrade");
+            let doc = format!(
+                "Upgrades an upgradable read guard of `{}` to a write guard.",
+                name.unraw()
+            );
+            code.extend(if is_async {
+                quote! {
+                    #[doc = #doc]
+                    #attrs
+                    pub fn #fn_name<'a>(
+                        &'a mut self,
+                        guard: #upgradable_guard,
+                    ) -> impl ::std::future::Future<
+                        Output = #write_guard
+                    > + 'a {
+                        <#declaration as ::locktree::#lock>::upgrade(guard)
+                    }
+                }
+            } else {
+                quote! {
+                    #[doc = #doc]
+                    #attrs
+                    pub fn #fn_name(
+                        &mut self,
+                        guard: #upgradable_guard,
+                    ) -> #write_guard {
+                        <#declaration as ::locktree::#lock>::upgrade(guard)
+                    }
+                }
+            });
+        }
+
+        code
+    }
+
+    /// Functions narrowing the guards of this lock to a part of the locked
+    /// data, for locks implementing the mappable trait. On the entry point
+    /// they take guards returned by the entry point, and on the lock's own
+    /// forward locktree the guard returned along with it.
+    fn map_functions(
+        &self,
+        is_async: bool,
+        name: &proc_macro2::Ident,
+        declaration: &TokenStream,
+        kind: AccessorKind,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        let async_keyword = if is_async { "Async" } else { "" };
+        let (lock, methods): (_, &[_]) = match self {
+            Self::Mutex => {
+                ("Mutex", &[("map", "Guard", "MappedGuard", "a guard", true)])
+            }
+            Self::RwLock | Self::UpgradableRwLock => (
+                "RwLock",
+                &[
+                    (
+                        "map_read",
+                        "ReadGuard",
+                        "MappedReadGuard",
+                        "a read guard",
+                        false,
+                    ),
+                    (
+                        "map_write",
+                        "WriteGuard",
+                        "MappedWriteGuard",
+                        "a write guard",
+                        true,
+                    ),
+                ],
+            ),
+            Self::Tree | Self::Shared => return TokenStream::new(),
+        };
+        let mappable = proc_macro2::Ident::new(
+            &format!("{}Mappable{}", async_keyword, lock),
+            proc_macro2::Span::call_site(),
+        );
+        let lock = proc_macro2::Ident::new(
+            &format!("{}{}", async_keyword, lock),
+            proc_macro2::Span::call_site(),
+        );
+        let (lifetime, fn_lifetime, receiver) = match kind {
+            AccessorKind::Entry => (
+                quote! {
+                    'a
+                },
+                quote! {
+                    'a,
+                },
+                quote! {
+                    &'a self
+                },
+            ),
+            AccessorKind::Forward | AccessorKind::ForwardInto => (
+                quote! {
+                    'b
+                },
+                TokenStream::new(),
+                quote! {
+                    &self
+                },
+            ),
+        };
+
+        let mut code = TokenStream::new();
+        for (method, guard, mapped, guard_doc, is_mut) in methods {
+            let guard =
+                guard_type(is_async, &lock, guard, declaration, &lifetime);
+            let mapped =
+                proc_macro2::Ident::new(mapped, proc_macro2::Span::call_site());
+            let mutability = if *is_mut {
+                quote! {
+                    mut
+                }
+            } else {
+                TokenStream::new()
+            };
+            let map_fn =
+                proc_macro2::Ident::new(method, proc_macro2::Span::call_site());
+            let try_map_fn = proc_macro2::Ident::new(
+                &format!("try_{}", method),
+                proc_macro2::Span::call_site(),
+            );
+            let fn_name = |method: &str| {
+                proc_macro2::Ident::new(
+                    &format!("{}_{}", method, name.unraw()),
                     proc_macro2::Span::call_site(),
-                );
+                )
+            };
+            let map_fn_name = fn_name(method);
+            let try_map_fn_name = fn_name(&format!("try_{}", method));
+            let map_doc = format!(
+                "Narrows {} of `{}` to a part of the locked data.",
+                guard_doc,
+                name.unraw()
+            );
+            let try_map_doc = format!(
+                "Like `{}`, but gives the guard back if `f` returns `None`.",
+                map_fn_name
+            );
+            // Only usable if the lock implements the trait, like downgrades.
+            code.extend(quote! {
+                #[doc = #map_doc]
+                #attrs
+                pub fn #map_fn_name<#fn_lifetime U, F>(
+                    #receiver,
+                    guard: #guard,
+                    f: F,
+                ) -> <#declaration as ::locktree::#mappable>::#mapped<#lifetime, U>
+                where
+                    for<'x> #declaration: ::locktree::#mappable,
+                    U: ?Sized + #lifetime,
+                    F: for<'x> ::std::ops::FnOnce(
+                        &'x #mutability <#declaration as ::locktree::#mappable>::Target
+                    ) -> &'x #mutability U,
+                {
+                    <#declaration as ::locktree::#mappable>::#map_fn(guard, f)
+                }
+
+                #[doc = #try_map_doc]
+                #attrs
+                pub fn #try_map_fn_name<#fn_lifetime U, F>(
+                    #receiver,
+                    guard: #guard,
+                    f: F,
+                ) -> ::std::result::Result<
+                    <#declaration as ::locktree::#mappable>::#mapped<#lifetime, U>,
+                    #guard
+                >
+                where
+                    for<'x> #declaration: ::locktree::#mappable,
+                    U: ?Sized + #lifetime,
+                    F: for<'x> ::std::ops::FnOnce(
+                        &'x #mutability <#declaration as ::locktree::#mappable>::Target
+                    ) -> ::std::option::Option<&'x #mutability U>,
+                {
+                    <#declaration as ::locktree::#mappable>::#try_map_fn(guard, f)
+                }
+            });
+        }
+
+        code
+    }
+
+    /// Non-blocking accessors for locks that don't come after the current
+    /// lock. They don't return a forward locktree, and borrow the current one
+    /// so nothing else can be locked through it while their guard is alive.
+    fn out_of_order_functions(
+        &self,
+        is_async: bool,
+        name: &proc_macro2::Ident,
+        declaration: &TokenStream,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        let lock = |trait_name: &str, method: &str, guard: &str| {
+            let fn_name = proc_macro2::Ident::new(
+                &format!("{}_{}_out_of_order", method, name.unraw()),
+                proc_macro2::Span::call_site(),
+            );
+            let trait_name = proc_macro2::Ident::new(
+                &format!(
+                    "{}{}",
+                    if is_async { "Async" } else { "" },
+                    trait_name
+                ),
+                proc_macro2::Span::call_site(),
+            );
+            let method =
+                proc_macro2::Ident::new(method, proc_macro2::Span::call_site());
+            let guard = guard_type(
+                is_async,
+                &trait_name,
+                guard,
+                declaration,
+                &quote! {
+                    'a
+                },
+            );
+            let doc = format!(
+                "Tries to lock `{}`{} without waiting, although it doesn't \
+                 come after the locks taken through this forward locktree.",
+                name.unraw(),
+                lock_purpose(method.to_string().trim_start_matches("try_")),
+            );
+
+            quote! {
+                #[doc = #doc]
+                #attrs
+                pub fn #fn_name<'a>(
+                    &'a self
+                ) -> ::std::result::Result<#guard, ::locktree::WouldBlock> {
+                    ::std::option::Option::ok_or(
+                        ::locktree::#trait_name::#method(&self.locks.#name),
+                        ::locktree::WouldBlock,
+                    )
+                }
+            }
+        };
+
+        match self {
+            Self::Mutex => lock("Mutex", "try_lock", "Guard"),
+            Self::RwLock | Self::UpgradableRwLock => {
+                let mut code = lock("RwLock", "try_read", "ReadGuard");
+                code.extend(lock("RwLock", "try_write", "WriteGuard"));
+
+                code
+            }
+            Self::Tree | Self::Shared => TokenStream::new(),
+        }
+    }
+
+    /// Accessor of a shared field on forward locktrees. The reference lives as
+    /// long as the forward's borrow of the tree.
+    fn shared_function(
+        &self,
+        name: &proc_macro2::Ident,
+        declaration: &TokenStream,
+        attrs: &TokenStream,
+    ) -> TokenStream {
+        match self {
+            Self::Shared => {
+                let doc = format!("Shared field `{}`.", name.unraw());
 
                 quote! {
-                    pub fn #read_fn_name<'a>(
-                        &'a #mut_keyword self
-                    ) -> (
-                        ::locktree::#read_guard<'a, #declaration>,
-                        #forward<'a>
-                    ) {
-                        (::locktree::#lock::read(&#accessor.#name), #forward { locks: #accessor })
+                    #[doc = #doc]
+                    #attrs
+                    pub fn #name(&self) -> &'b #declaration {
+                        &self.locks.#name
+                    }
+                }
+            }
+            _ => TokenStream::new(),
+        }
+    }
+}
+
+/// The different flavors of accessor functions.
+#[derive(Clone, Copy)]
+enum AccessorKind {
+    /// Accessors of the entry point, taking `&self`.
+    Entry,
+    /// Accessors of forward locktrees, taking `&mut self`.
+    Forward,
+    /// Accessors of forward locktrees that consume the forward, keeping its
+    /// lifetime. Used to chain several locks in a single expression.
+    ForwardInto,
+}
+
+/// Accessor functions of a lock, on each struct that can take it.
+struct Accessors {
+    entry: TokenStream,
+    forward: TokenStream,
+    forward_into: TokenStream,
+    /// Accessors of the entry point with a deadline.
+    deadline_entry: TokenStream,
+    /// Accessors of the forward locktrees keeping a deadline.
+    deadline_forward: TokenStream,
+    deadline_forward_into: TokenStream,
+}
+
+/// Everything about an accessor function that does not depend on the type of
+/// lock.
+struct AccessorContext<'a> {
+    kind: AccessorKind,
+    fn_prefix: &'a str,
+    fn_generics: TokenStream,
+    lifetime: TokenStream,
+    receiver: TokenStream,
+    accessor: TokenStream,
+    /// Forward locktree returned by the accessors, which keeps the deadline,
+    /// if any.
+    forward: proc_macro2::Ident,
+    forward_type: TokenStream,
+    /// Forward locktree returned by the accessors waiting until a deadline.
+    deadline_forward: proc_macro2::Ident,
+    deadline_forward_type: TokenStream,
+    /// Fields of the forward locktree other than `deadline`.
+    forward_fields: TokenStream,
+    /// Deadline of the entry point or forward locktree, if it has one.
+    deadline: Option<TokenStream>,
+    attrs: &'a TokenStream,
+    is_async: bool,
+}
+
+impl AccessorContext<'_> {
+    /// Accessor function returning the guard produced by `lock` along with
+    /// the forward locktree. `args` are added after the receiver, and
+    /// `where_clause` restricts the accessor to locks that support it.
+    fn function(
+        &self,
+        name: &str,
+        doc: &str,
+        args: TokenStream,
+        guard: TokenStream,
+        lock: TokenStream,
+        where_clause: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            fn_generics,
+            receiver,
+            forward_type,
+            attrs,
+            ..
+        } = self;
+        let fn_name = self.fn_name(name);
+        let doc = self.doc(name, doc);
+        let forward_init = self.forward_init();
+
+        quote! {
+            #doc
+            #attrs
+            pub fn #fn_name #fn_generics(
+                #receiver #args
+            ) -> (
+                #guard,
+                #forward_type
+            )
+            #where_clause
+            {
+                (#lock, #forward_init)
+            }
+        }
+    }
+
+    /// Accessor function entering a nested tree, which only returns the
+    /// forward locktree. The inner tree is then reached through the forward,
+    /// so that its guards keep the forward borrowed.
+    fn enter_function(&self, name: &str, doc: &str) -> TokenStream {
+        let Self {
+            fn_generics,
+            receiver,
+            forward_type,
+            attrs,
+            ..
+        } = self;
+        let fn_name = self.fn_name(name);
+        let doc = self.doc(name, doc);
+        let forward_init = self.forward_init();
+
+        quote! {
+            #doc
+            #attrs
+            pub fn #fn_name #fn_generics(#receiver) -> #forward_type {
+                #forward_init
+            }
+        }
+    }
+
+    /// Like `function`, but `lock` returns an `Option` and so does the
+    /// accessor.
+    fn try_function(
+        &self,
+        name: &str,
+        doc: &str,
+        guard: TokenStream,
+        lock: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            fn_generics,
+            receiver,
+            forward_type,
+            attrs,
+            ..
+        } = self;
+        let fn_name = self.fn_name(name);
+        let doc = self.doc(name, doc);
+        let forward_init = self.forward_init();
+
+        quote! {
+            #doc
+            #attrs
+            pub fn #fn_name #fn_generics(
+                #receiver
+            ) -> ::std::option::Option<(
+                #guard,
+                #forward_type
+            )> {
+                match #lock {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, #forward_init))
                     }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+    }
 
-                    pub fn #write_fn_name<'a>(
-                        &'a #mut_keyword self
-                    ) -> (
-                        ::locktree::#write_guard<'a, #declaration>,
-                        #forward<'a>
-                    ) {
-                        (::locktree::#lock::write(&#accessor.#name), #forward { locks: #accessor })
+    /// Like `function`, but `lock` returns a `Result` with `TimedOut` as the
+    /// error and so does the accessor.
+    fn timed_function(
+        &self,
+        name: &str,
+        doc: &str,
+        args: TokenStream,
+        guard: TokenStream,
+        lock: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            fn_generics,
+            receiver,
+            forward_type,
+            attrs,
+            ..
+        } = self;
+        let fn_name = self.fn_name(name);
+        let doc = self.doc(name, doc);
+        let forward_init = self.forward_init();
+
+        quote! {
+            #doc
+            #attrs
+            pub fn #fn_name #fn_generics(
+                #receiver #args
+            ) -> ::std::result::Result<(
+                #guard,
+                #forward_type
+            ), ::locktree::TimedOut> {
+                match #lock {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, #forward_init))
                     }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
                 }
             }
         }
     }
+
+    /// Like `timed_function`, but waits until the deadline of the entry point
+    /// or forward locktree, or until `deadline` if it has none. The returned
+    /// forward keeps that deadline.
+    fn deadline_function(
+        &self,
+        name: &str,
+        doc: &str,
+        lock_name: &str,
+        guard: TokenStream,
+        lock_until: TokenStream,
+        where_clause: TokenStream,
+    ) -> TokenStream {
+        let Self {
+            fn_generics,
+            receiver,
+            deadline_forward_type,
+            attrs,
+            is_async,
+            ..
+        } = self;
+        let (args, binding) = match &self.deadline {
+            Some(deadline) => (
+                TokenStream::new(),
+                quote! {
+                    let deadline = #deadline;
+                },
+            ),
+            None => (
+                quote! {
+                    , deadline: ::std::time::Instant
+                },
+                TokenStream::new(),
+            ),
+        };
+        let fn_name = self.fn_name(name);
+        let doc = self.doc(name, doc);
+        let forward_init = self.deadline_forward_init(&quote! {
+            deadline
+        });
+
+        if *is_async {
+            quote! {
+                #doc
+                #attrs
+                pub fn #fn_name #fn_generics(
+                    #receiver #args
+                ) -> (
+                    #guard,
+                    #deadline_forward_type
+                )
+                #where_clause
+                {
+                    #binding
+                    (
+                        ::locktree::deadline::lock_async(#lock_name, #lock_until),
+                        #forward_init,
+                    )
+                }
+            }
+        } else {
+            quote! {
+                #doc
+                #attrs
+                pub fn #fn_name #fn_generics(
+                    #receiver #args
+                ) -> ::std::result::Result<(
+                    #guard,
+                    #deadline_forward_type
+                ), ::locktree::DeadlineExceeded>
+                #where_clause
+                {
+                    #binding
+                    match ::locktree::deadline::lock(#lock_name, #lock_until) {
+                        ::std::result::Result::Ok(guard) => {
+                            ::std::result::Result::Ok((guard, #forward_init))
+                        }
+                        ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Initialization of the returned forward locktree, keeping the deadline
+    /// if there is one.
+    fn forward_init(&self) -> TokenStream {
+        let Self {
+            forward,
+            forward_fields,
+            ..
+        } = self;
+
+        match &self.deadline {
+            Some(deadline) => self.deadline_forward_init(deadline),
+            None => quote! {
+                #forward { #forward_fields }
+            },
+        }
+    }
+
+    fn deadline_forward_init(&self, deadline: &TokenStream) -> TokenStream {
+        let Self {
+            deadline_forward,
+            forward_fields,
+            ..
+        } = self;
+
+        quote! {
+            #deadline_forward { #forward_fields, deadline: #deadline }
+        }
+    }
+
+    /// Generated documentation of the accessor `name`. Consuming accessors
+    /// refer to the accessor they mirror instead.
+    fn doc(&self, name: &str, doc: &str) -> TokenStream {
+        let doc = match self.kind {
+            AccessorKind::Entry | AccessorKind::Forward => doc.to_string(),
+            AccessorKind::ForwardInto => {
+                format!("Like `{}`, but consumes this forward locktree.", name)
+            }
+        };
+
+        quote! {
+            #[doc = #doc]
+        }
+    }
+
+    fn fn_name(&self, name: &str) -> proc_macro2::Ident {
+        proc_macro2::Ident::new(
+            &format!("{}{}", self.fn_prefix, name),
+            proc_macro2::Span::call_site(),
+        )
+    }
 }
 
 impl Parse for LockInterface {
     fn parse(input: ParseStream) -> Result<Self> {
         custom_keyword!(Mutex);
         custom_keyword!(RwLock);
+        custom_keyword!(UpgradableRwLock);
+        custom_keyword!(Tree);
+        custom_keyword!(Shared);
 
         let lookahead = input.lookahead1();
         if lookahead.peek(Mutex) {
@@ -307,6 +2206,18 @@ impl Parse for LockInterface {
             input.parse::<RwLock>().unwrap();
 
             Ok(Self::RwLock)
+        } else if lookahead.peek(UpgradableRwLock) {
+            input.parse::<UpgradableRwLock>().unwrap();
+
+            Ok(Self::UpgradableRwLock)
+        } else if lookahead.peek(Tree) {
+            input.parse::<Tree>().unwrap();
+
+            Ok(Self::Tree)
+        } else if lookahead.peek(Shared) {
+            input.parse::<Shared>().unwrap();
+
+            Ok(Self::Shared)
         } else {
             Err(lookahead.error())
         }
@@ -316,10 +2227,33 @@ impl Parse for LockInterface {
 struct Fragment {
     main_accessors: TokenStream,
     forward_accessors: TokenStream,
+    forward_into_accessors: TokenStream,
+    /// Accessors of the entry point with a deadline.
+    deadline_accessors: TokenStream,
+    deadline_forward_accessors: TokenStream,
+    deadline_forward_into_accessors: TokenStream,
+    out_of_order_accessors: TokenStream,
+    /// `downgrade_x`, `upgrade_x` and `map_x` functions of this lock's own
+    /// forward locktree, or its accessor to a nested tree, if any.
+    conversion_functions: TokenStream,
+    /// Like `conversion_functions`, for the forward locktree keeping a
+    /// deadline.
+    deadline_conversion_functions: TokenStream,
+    /// Accessor of a shared field, available on every forward locktree.
+    shared_accessor: TokenStream,
     forward: String,
     lock_declaration: TokenStream,
     init_arg: TokenStream,
     init_statement: TokenStream,
+    /// Binding of the default value to the argument of `new`, if any.
+    default_statement: Option<TokenStream>,
+    builder_field: TokenStream,
+    builder_init: TokenStream,
+    builder_setter: TokenStream,
+    /// Binding of the value given to the builder, or of the default value, to
+    /// the argument of `new`.
+    builder_statement: TokenStream,
+    cfg_attrs: TokenStream,
 }
 
 #[proc_macro]
@@ -328,57 +2262,338 @@ pub fn locktree(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 fn locktree_impl(input: TokenStream) -> TokenStream {
-    let map = syn::parse2::<LockTree>(input).unwrap().map;
+    let map = match syn::parse2::<LockTree>(input) {
+        Ok(tree) => tree.map,
+        Err(error) => return error.to_compile_error(),
+    };
     let mut code = TokenStream::new();
-    for (struct_name, LockSequence { seq }) in map {
-        let struct_prefix = format!("{}LockTree", struct_name);
+    for (
+        struct_name,
+        LockSequence {
+            attrs,
+            vis,
+            is_exclusive,
+            generics,
+            seq,
+        },
+    ) in map
+    {
+        let struct_prefix = format!("{}LockTree", struct_name.unraw());
         let main_struct = proc_macro2::Ident::new(
             &struct_prefix,
             proc_macro2::Span::call_site(),
         );
-        let fragments = seq
-            .into_iter()
-            .map(|x| x.fragment(&struct_prefix))
+        let (impl_generics, ty_generics, where_clause) =
+            generics.split_for_impl();
+        let forward_args = generics.params.iter().map(|x| match x {
+            GenericParam::Type(x) => x.ident.to_token_stream(),
+            GenericParam::Lifetime(x) => x.lifetime.to_token_stream(),
+            GenericParam::Const(x) => x.ident.to_token_stream(),
+        });
+        let forward_args = quote! {
+            #(, #forward_args)*
+        };
+        let locks = flatten(&seq);
+        let fragments = locks
+            .iter()
+            .map(|x| {
+                x.lock.fragment(
+                    &struct_prefix,
+                    &forward_args,
+                    is_exclusive,
+                    &x.inherited_cfg_attrs,
+                )
+            })
             .collect::<Vec<_>>();
 
         let init_args = fragments.iter().map(|x| &x.init_arg);
-        let init_statements = fragments.iter().map(|x| &x.init_statement);
+        let init_statements = fragments
+            .iter()
+            .map(|x| &x.init_statement)
+            .collect::<Vec<_>>();
+        let builder_struct = proc_macro2::Ident::new(
+            &format!("{}Builder", struct_prefix),
+            proc_macro2::Span::call_site(),
+        );
+        let builder_inits = fragments.iter().map(|x| &x.builder_init);
+        let builder_fn = if has_builder(&attrs) {
+            let doc = format!(
+                "Starts building the locktree with `{}`.",
+                builder_struct
+            );
+
+            Some(quote! {
+                #[doc = #doc]
+                #vis fn builder() -> #builder_struct #ty_generics {
+                    #builder_struct {
+                        #(#builder_inits)*
+                    }
+                }
+            })
+        } else {
+            None
+        };
         let init_fn = quote! {
-            pub fn new(#(#init_args),*) -> Self {
+            #[doc = "Creates the locktree from the initial value of each lock."]
+            #vis fn new(#(#init_args),*) -> Self {
                 Self {
                     #(#init_statements)*
                 }
             }
+
+            #builder_fn
         };
 
+        let item_attrs = select_attributes(&attrs, &["allow", "cfg"]);
+        let item_attrs = quote! {
+            #(#item_attrs)*
+        };
+        let struct_attrs = select_attributes(&attrs, &["derive", "doc"]);
         let main_accessors = fragments.iter().map(|x| &x.main_accessors);
         let lock_declarations = fragments.iter().map(|x| &x.lock_declaration);
         code.extend(quote! {
-            struct #main_struct {
+            #item_attrs
+            #(#struct_attrs)*
+            #vis struct #main_struct #generics #where_clause {
                 #(#lock_declarations)*
             }
 
-            impl #main_struct {
+            #item_attrs
+            impl #impl_generics #main_struct #ty_generics #where_clause {
                 #init_fn
 
                 #(#main_accessors)*
             }
         });
 
+        if implements_default(&seq) {
+            let default_statements =
+                fragments.iter().map(|x| &x.default_statement);
+            code.extend(quote! {
+                #item_attrs
+                impl #impl_generics ::std::default::Default
+                    for #main_struct #ty_generics #where_clause
+                {
+                    fn default() -> Self {
+                        #(#default_statements)*
+                        Self {
+                            #(#init_statements)*
+                        }
+                    }
+                }
+            });
+        }
+
+        if builder_fn.is_some() {
+            let builder_fields = fragments.iter().map(|x| &x.builder_field);
+            let builder_setters = fragments.iter().map(|x| &x.builder_setter);
+            let builder_statements =
+                fragments.iter().map(|x| &x.builder_statement);
+            let doc = format!(
+                "Builder of `{}`, taking the initial value of each lock.",
+                main_struct
+            );
+            let build_doc = "Builds the locktree. Locks that weren't set take \
+                             their default value, and `MissingValue` is \
+                             returned for those without one.";
+            // Locks with a default value don't need to be set, so their
+            // setters shouldn't warn when unused.
+            code.extend(quote! {
+                #[doc = #doc]
+                #item_attrs
+                #vis struct #builder_struct #generics #where_clause {
+                    #(#builder_fields)*
+                }
+
+                #item_attrs
+                #[allow(dead_code)]
+                impl #impl_generics #builder_struct #ty_generics #where_clause {
+                    #(#builder_setters)*
+
+                    #[doc = #build_doc]
+                    #vis fn build(
+                        self
+                    ) -> ::std::result::Result<
+                        #main_struct #ty_generics,
+                        ::locktree::MissingValue
+                    > {
+                        #(#builder_statements)*
+                        ::std::result::Result::Ok(#main_struct {
+                            #(#init_statements)*
+                        })
+                    }
+                }
+            });
+        }
+
+        let mut forward_generics = generics.clone();
+        forward_generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeDef::new(Lifetime::new(
+                "'b",
+                proc_macro2::Span::call_site(),
+            ))),
+        );
+        let (forward_impl_generics, forward_ty_generics, _) =
+            forward_generics.split_for_impl();
+        let token_declaration = if is_exclusive {
+            Some(quote! {
+                , token: &'b mut ::locktree::LockToken
+            })
+        } else {
+            None
+        };
+        let deadline_struct = proc_macro2::Ident::new(
+            &format!("{}Deadline", struct_prefix),
+            proc_macro2::Span::call_site(),
+        );
+        let doc = format!(
+            "Entry point of `{}` whose accessors give up at a deadline, \
+             returned by `WithDeadline::with_deadline`.",
+            main_struct
+        );
+        let deadline_accessors =
+            fragments.iter().map(|x| &x.deadline_accessors);
+        code.extend(quote! {
+            #[doc = #doc]
+            #item_attrs
+            #vis struct #deadline_struct #forward_generics #where_clause {
+                locks: &'b #main_struct #ty_generics,
+                deadline: ::std::time::Instant
+            }
+
+            #item_attrs
+            impl #forward_impl_generics #deadline_struct #forward_ty_generics
+                #where_clause
+            {
+                #(#deadline_accessors)*
+            }
+
+            #item_attrs
+            impl #impl_generics ::locktree::WithDeadline
+                for #main_struct #ty_generics #where_clause
+            {
+                type Deadline<'b> = #deadline_struct #forward_ty_generics
+                where
+                    Self: 'b;
+
+                fn with_deadline(
+                    &self,
+                    deadline: ::std::time::Instant
+                ) -> Self::Deadline<'_> {
+                    #deadline_struct { locks: self, deadline }
+                }
+            }
+        });
+
+        let shared_accessors = fragments
+            .iter()
+            .map(|x| &x.shared_accessor)
+            .collect::<Vec<_>>();
         for (i, fragment) in fragments.iter().enumerate() {
+            // Shared fields aren't locked, so they have no forward locktree.
+            if let LockInterface::Shared = locks[i].lock.ty.interface {
+                continue;
+            }
+
             let name = proc_macro2::Ident::new(
                 &fragment.forward,
                 proc_macro2::Span::call_site(),
             );
-            let forward_accessors =
-                fragments[i + 1..].iter().map(|x| &x.forward_accessors);
+            let forward_accessors = fragments
+                .iter()
+                .zip(&locks)
+                .filter(|(_, lock)| locks[i].precedes(lock))
+                .map(|(x, _)| &x.forward_accessors);
+            let forward_into_accessors = fragments
+                .iter()
+                .zip(&locks)
+                .filter(|(_, lock)| locks[i].precedes(lock))
+                .map(|(x, _)| &x.forward_into_accessors);
+            let deadline_forward_accessors = fragments
+                .iter()
+                .zip(&locks)
+                .filter(|(_, lock)| locks[i].precedes(lock))
+                .map(|(x, _)| &x.deadline_forward_accessors);
+            let deadline_forward_into_accessors = fragments
+                .iter()
+                .zip(&locks)
+                .filter(|(_, lock)| locks[i].precedes(lock))
+                .map(|(x, _)| &x.deadline_forward_into_accessors);
+            let out_of_order_accessors = fragments
+                .iter()
+                .zip(&locks)
+                .filter(|(_, lock)| !locks[i].precedes(lock))
+                .map(|(x, _)| &x.out_of_order_accessors)
+                .collect::<Vec<_>>();
+            let conversion_functions = &fragment.conversion_functions;
+            let deadline_conversion_functions =
+                &fragment.deadline_conversion_functions;
+            let cfg_attrs = &fragment.cfg_attrs;
+            let following = locks
+                .iter()
+                .filter(|lock| {
+                    locks[i].precedes(lock)
+                        && !matches!(
+                            lock.lock.ty.interface,
+                            LockInterface::Shared
+                        )
+                })
+                .collect::<Vec<_>>();
+            let deadline_name = proc_macro2::Ident::new(
+                &format!("{}Deadline", fragment.forward),
+                proc_macro2::Span::call_site(),
+            );
+            let doc = forward_doc(locks[i].lock, &following, false);
+            let deadline_doc = forward_doc(locks[i].lock, &following, true);
             code.extend(quote! {
-                struct #name<'b> {
-                    locks: &'b #main_struct
+                #doc
+                #item_attrs
+                #cfg_attrs
+                #vis struct #name #forward_generics #where_clause {
+                    locks: &'b #main_struct #ty_generics
+                    #token_declaration
                 }
 
-                impl<'b> #name<'b> {
+                #item_attrs
+                #cfg_attrs
+                impl #forward_impl_generics #name #forward_ty_generics
+                    #where_clause
+                {
+                    #(#shared_accessors)*
+
                     #(#forward_accessors)*
+
+                    #(#forward_into_accessors)*
+
+                    #(#out_of_order_accessors)*
+
+                    #conversion_functions
+                }
+
+                #deadline_doc
+                #item_attrs
+                #cfg_attrs
+                #vis struct #deadline_name #forward_generics #where_clause {
+                    locks: &'b #main_struct #ty_generics
+                    #token_declaration,
+                    deadline: ::std::time::Instant
+                }
+
+                #item_attrs
+                #cfg_attrs
+                impl #forward_impl_generics #deadline_name #forward_ty_generics
+                    #where_clause
+                {
+                    #(#shared_accessors)*
+
+                    #(#deadline_forward_accessors)*
+
+                    #(#deadline_forward_into_accessors)*
+
+                    #(#out_of_order_accessors)*
+
+                    #deadline_conversion_functions
                 }
             });
         }
@@ -387,12 +2602,687 @@ fn locktree_impl(input: TokenStream) -> TokenStream {
     code
 }
 
+/// Documentation of the forward locktree of `lock`, listing the locks that can
+/// still be taken through it. Each lock is listed under its own `cfg`
+/// attributes, so that locks that are compiled out aren't mentioned.
+fn forward_doc(
+    lock: &Lock,
+    following: &[&FlatLock],
+    has_deadline: bool,
+) -> TokenStream {
+    let action = match lock.ty.interface {
+        LockInterface::Tree => "entering",
+        _ => "locking",
+    };
+    let summary = format!(
+        "Forward locktree after {} `{}`{}.",
+        action,
+        lock.name.unraw(),
+        if has_deadline {
+            ", whose accessors give up at a deadline"
+        } else {
+            ""
+        },
+    );
+    let none = "It can't take any other lock.";
+    if following.is_empty() {
+        let doc = format!("{} {}", summary, none);
+
+        return quote! {
+            #[doc = #doc]
+        };
+    }
+
+    let mut predicates = Vec::new();
+    let mut items = TokenStream::new();
+    for x in following {
+        let item = format!("- `{}`", x.lock.name.unraw());
+        items.extend(match x.cfg_predicate() {
+            Some(predicate) => {
+                let item = quote! {
+                    #[cfg_attr(#predicate, doc = #item)]
+                };
+                predicates.push(predicate);
+
+                item
+            }
+            None => quote! {
+                #[doc = #item]
+            },
+        });
+    }
+    let header = "It can still take:";
+    // When every following lock can be compiled out, the list is only
+    // introduced in the configurations where one of them remains.
+    let header = if predicates.len() == following.len() {
+        quote! {
+            #[cfg_attr(any(#(#predicates),*), doc = #header)]
+            #[cfg_attr(not(any(#(#predicates),*)), doc = #none)]
+        }
+    } else {
+        quote! {
+            #[doc = #header]
+        }
+    };
+
+    quote! {
+        #[doc = #summary]
+        #[doc = ""]
+        #header
+        #items
+    }
+}
+
+/// Input of `lock_many!`: an expression returning the first guard and its
+/// forward locktree, followed by the names of the accessors to chain.
+struct LockMany {
+    first: Expr,
+    accessors: Vec<Ident>,
+}
+
+impl Parse for LockMany {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let first = input.parse()?;
+        let mut accessors = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            accessors.push(input.parse()?);
+        }
+
+        Ok(Self { first, accessors })
+    }
+}
+
+#[proc_macro]
+pub fn lock_many(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    lock_many_impl(input.into()).into()
+}
+
+fn lock_many_impl(input: TokenStream) -> TokenStream {
+    let LockMany { first, accessors } = match syn::parse2(input) {
+        Ok(x) => x,
+        Err(error) => return error.to_compile_error(),
+    };
+    let forward =
+        proc_macro2::Ident::new("forward", proc_macro2::Span::mixed_site());
+    let guards = (0..=accessors.len())
+        .map(|i| {
+            proc_macro2::Ident::new(
+                &format!("guard{}", i),
+                proc_macro2::Span::mixed_site(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let first_guard = &guards[0];
+    let next_guards = &guards[1..];
+    let accessors = accessors
+        .iter()
+        .map(|x| proc_macro2::Ident::new(&format!("into_{}", x), x.span()));
+
+    quote! {
+        {
+            let (#first_guard, #forward) = #first;
+            #(let (#next_guards, #forward) = #forward.#accessors();)*
+            ((#(#guards,)*), #forward)
+        }
+    }
+}
+
+/// Lock trait implemented by `backend!`.
+#[derive(Clone, Copy)]
+enum BackendInterface {
+    Mutex,
+    RwLock,
+    AsyncMutex,
+    AsyncRwLock,
+}
+
+impl BackendInterface {
+    /// Guard types, which must all be given.
+    fn types(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mutex | Self::AsyncMutex => &["Guard"],
+            Self::RwLock | Self::AsyncRwLock => &["ReadGuard", "WriteGuard"],
+        }
+    }
+
+    /// Lock methods, along with their guard type.
+    fn methods(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Mutex | Self::AsyncMutex => &[("lock", "Guard")],
+            Self::RwLock | Self::AsyncRwLock => {
+                &[("read", "ReadGuard"), ("write", "WriteGuard")]
+            }
+        }
+    }
+
+    fn is_async(&self) -> bool {
+        matches!(self, Self::AsyncMutex | Self::AsyncRwLock)
+    }
+
+    fn is_rw_lock(&self) -> bool {
+        matches!(self, Self::RwLock | Self::AsyncRwLock)
+    }
+}
+
+impl Parse for BackendInterface {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Ident>()?;
+        match name.to_string().as_str() {
+            "Mutex" => Ok(Self::Mutex),
+            "RwLock" => Ok(Self::RwLock),
+            "AsyncMutex" => Ok(Self::AsyncMutex),
+            "AsyncRwLock" => Ok(Self::AsyncRwLock),
+            _ => Err(syn::Error::new_spanned(
+                name,
+                "expected one of: `Mutex`, `RwLock`, `AsyncMutex`, \
+                 `AsyncRwLock`",
+            )),
+        }
+    }
+}
+
+/// Input of `backend!`: a lock type with the lock trait to implement, its
+/// guard types, and the names of its methods where they differ from the
+/// trait's.
+struct Backend {
+    generics: Generics,
+    interface: BackendInterface,
+    lock: Type,
+    value: Type,
+    types: HashMap<String, (Lifetime, Type)>,
+    methods: HashMap<String, Ident>,
+    /// Function downgrading write guards, if the lock supports it.
+    downgrade: Option<Path>,
+}
+
+impl Parse for Backend {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut errors = Errors::default();
+        input.parse::<Token![impl]>()?;
+        let mut generics = input.parse::<Generics>()?;
+        let interface = input.parse::<BackendInterface>()?;
+        input.parse::<Token![for]>()?;
+        let lock = input.parse::<Type>()?;
+        generics.where_clause = input.parse()?;
+        let value = match &lock {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .and_then(|segment| match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => args.args.last(),
+                    _ => None,
+                })
+                .and_then(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty.clone()),
+                    _ => None,
+                }),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &lock,
+                "expected a lock type taking the locked data as its last \
+                 generic argument",
+            )
+        })?;
+
+        let body;
+        braced!(body in input);
+        let mut types = HashMap::new();
+        let mut methods = HashMap::new();
+        let mut downgrade = None;
+        while !body.is_empty() {
+            let lookahead = body.lookahead1();
+            if lookahead.peek(Token![type]) {
+                body.parse::<Token![type]>()?;
+                let name = body.parse::<Ident>()?;
+                body.parse::<Token![<]>()?;
+                let lifetime = body.parse::<Lifetime>()?;
+                body.parse::<Token![>]>()?;
+                body.parse::<Token![=]>()?;
+                let ty = body.parse::<Type>()?;
+                body.parse::<Token![;]>()?;
+
+                let key = name.to_string();
+                if !interface.types().contains(&key.as_str()) {
+                    errors.push(syn::Error::new_spanned(
+                        &name,
+                        format!(
+                            "unexpected type, expected one of: {}",
+                            interface
+                                .types()
+                                .iter()
+                                .map(|x| format!("`{}`", x))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ),
+                    ));
+                } else if types.insert(key, (lifetime, ty)).is_some() {
+                    errors.push(syn::Error::new_spanned(
+                        &name,
+                        format!("duplicate `type {}`", name),
+                    ));
+                }
+            } else if lookahead.peek(Token![fn]) {
+                body.parse::<Token![fn]>()?;
+                let name = body.parse::<Ident>()?;
+                body.parse::<Token![=]>()?;
+                let key = name.to_string();
+                if key == "downgrade" && interface.is_rw_lock() {
+                    let function = body.parse::<Path>()?;
+                    body.parse::<Token![;]>()?;
+                    if downgrade.replace(function).is_some() {
+                        errors.push(syn::Error::new_spanned(
+                            &name,
+                            "duplicate `fn downgrade`",
+                        ));
+                    }
+                    continue;
+                }
+                let method = body.parse::<Ident>()?;
+                body.parse::<Token![;]>()?;
+
+                let expected = std::iter::once("new".to_string())
+                    .chain(interface.methods().iter().flat_map(
+                        |(method, _)| {
+                            let mut names = vec![
+                                method.to_string(),
+                                format!("try_{}", method),
+                            ];
+                            if interface.is_async() {
+                                names.push(format!("{}_until", method));
+                            }
+                            names
+                        },
+                    ))
+                    .chain(
+                        interface.is_rw_lock().then(|| "downgrade".to_string()),
+                    )
+                    .collect::<Vec<_>>();
+                if !expected.contains(&key) {
+                    errors.push(syn::Error::new_spanned(
+                        &name,
+                        format!(
+                            "unexpected method, expected one of: {}",
+                            expected
+                                .iter()
+                                .map(|x| format!("`{}`", x))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ),
+                    ));
+                } else if methods.insert(key, method).is_some() {
+                    errors.push(syn::Error::new_spanned(
+                        &name,
+                        format!("duplicate `fn {}`", name),
+                    ));
+                }
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+        for name in interface.types() {
+            if !types.contains_key(*name) {
+                errors.push(syn::Error::new(
+                    input.span(),
+                    format!("missing `type {}`", name),
+                ));
+            }
+        }
+
+        errors.finish(Self {
+            generics,
+            interface,
+            lock,
+            value,
+            types,
+            methods,
+            downgrade,
+        })
+    }
+}
+
+#[proc_macro]
+pub fn backend(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    backend_impl(input.into()).into()
+}
+
+fn backend_impl(input: TokenStream) -> TokenStream {
+    let Backend {
+        generics,
+        interface,
+        lock,
+        value,
+        types,
+        methods,
+        downgrade,
+    } = match syn::parse2(input) {
+        Ok(x) => x,
+        Err(error) => return error.to_compile_error(),
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let method = |name: &str| {
+        methods.get(name).cloned().unwrap_or_else(|| {
+            proc_macro2::Ident::new(name, proc_macro2::Span::call_site())
+        })
+    };
+    let trait_name = proc_macro2::Ident::new(
+        match interface {
+            BackendInterface::Mutex => "Mutex",
+            BackendInterface::RwLock => "RwLock",
+            BackendInterface::AsyncMutex => "AsyncMutex",
+            BackendInterface::AsyncRwLock => "AsyncRwLock",
+        },
+        proc_macro2::Span::call_site(),
+    );
+
+    let mut items = TokenStream::new();
+    for name in interface.types() {
+        if let Some((lifetime, ty)) = types.get(*name) {
+            let name =
+                proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
+            items.extend(quote! {
+                type #name<#lifetime> = #ty
+                where
+                    Self: #lifetime;
+            });
+        }
+    }
+    for (name, guard) in interface.methods() {
+        let fn_name =
+            proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
+        let try_fn_name = proc_macro2::Ident::new(
+            &format!("try_{}", name),
+            proc_macro2::Span::call_site(),
+        );
+        let lock_call = inherent_call(&lock, &method(name), quote! { self });
+        let try_lock_call = inherent_call(
+            &lock,
+            &method(&format!("try_{}", name)),
+            quote! { self },
+        );
+        let guard =
+            proc_macro2::Ident::new(guard, proc_macro2::Span::call_site());
+        items.extend(if interface.is_async() {
+            quote! {
+                fn #fn_name(
+                    &self
+                ) -> impl ::std::future::Future<
+                    Output = Self::#guard<'_>
+                > + '_ {
+                    #lock_call
+                }
+            }
+        } else {
+            quote! {
+                fn #fn_name(&self) -> Self::#guard<'_> {
+                    #lock_call
+                }
+            }
+        });
+        items.extend(quote! {
+            fn #try_fn_name(
+                &self
+            ) -> ::std::option::Option<Self::#guard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    #try_lock_call
+                )
+            }
+        });
+    }
+    // `async` locks only lock with a deadline if given a method for it.
+    let is_timed = interface.is_async()
+        && interface
+            .methods()
+            .iter()
+            .any(|(name, _)| methods.contains_key(&format!("{}_until", name)));
+    let timed_impl = is_timed.then(|| {
+        let trait_name = proc_macro2::Ident::new(
+            if interface.is_rw_lock() {
+                "AsyncTimedRwLock"
+            } else {
+                "AsyncTimedMutex"
+            },
+            proc_macro2::Span::call_site(),
+        );
+        let items = interface.methods().iter().map(|(name, guard)| {
+            let until_fn_name = proc_macro2::Ident::new(
+                &format!("{}_until", name),
+                proc_macro2::Span::call_site(),
+            );
+            let lock_until_call = inherent_call(
+                &lock,
+                &method(&format!("{}_until", name)),
+                quote! { self, deadline },
+            );
+            let guard =
+                proc_macro2::Ident::new(guard, proc_macro2::Span::call_site());
+            quote! {
+                fn #until_fn_name(
+                    &self,
+                    deadline: ::std::time::Instant
+                ) -> impl ::std::future::Future<
+                    Output = ::std::result::Result<
+                        Self::#guard<'_>,
+                        ::locktree::TimedOut
+                    >
+                > + '_ {
+                    ::locktree::backend::timed_out(
+                        #lock_until_call
+                    )
+                }
+            }
+        });
+
+        quote! {
+            impl #impl_generics ::locktree::#trait_name for #lock #where_clause {
+                #(#items)*
+            }
+        }
+    });
+    let new_call = inherent_call(&lock, &method("new"), quote! { value });
+    let downgrade_impl = downgrade.map(|function| {
+        let trait_name = proc_macro2::Ident::new(
+            &format!(
+                "{}DowngradableRwLock",
+                if interface.is_async() { "Async" } else { "" },
+            ),
+            proc_macro2::Span::call_site(),
+        );
+        quote! {
+            impl #impl_generics ::locktree::#trait_name for #lock #where_clause {
+                fn downgrade<'a>(
+                    guard: Self::WriteGuard<'a>
+                ) -> Self::ReadGuard<'a>
+                where
+                    Self: 'a,
+                {
+                    #function(guard)
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::locktree::New<#value> for #lock #where_clause {
+            fn new(value: #value) -> Self {
+                #new_call
+            }
+        }
+
+        impl #impl_generics ::locktree::#trait_name for #lock #where_clause {
+            #items
+        }
+
+        #timed_impl
+
+        #downgrade_impl
+    }
+}
+
+/// Call of the inherent method `method` of `lock`. Without such a method,
+/// the call would resolve to the lock trait method being implemented and
+/// recurse forever, so a local trait makes it ambiguous instead.
+fn inherent_call(
+    lock: &Type,
+    method: &proc_macro2::Ident,
+    args: TokenStream,
+) -> TokenStream {
+    quote! {
+        {
+            #[allow(dead_code)]
+            trait InherentMethod {
+                fn #method<A>(_: A) {}
+            }
+            impl<T: ?Sized> InherentMethod for T {}
+            <#lock>::#method(#args)
+        }
+    }
+}
+
+/// How the lock method `method` locks, to complete the generated
+/// documentation of its accessors.
+fn lock_purpose(method: &str) -> &'static str {
+    match method {
+        "read" => " for reading",
+        "write" => " for writing",
+        "upgradable_read" => " for an upgradable read",
+        _ => "",
+    }
+}
+
+/// Type of the guard `assoc` of the lock trait `lock`, spelled through its
+/// `Plugged` alias for blocking locks. `async` locks have no such aliases.
+fn guard_type(
+    is_async: bool,
+    lock: &proc_macro2::Ident,
+    assoc: &str,
+    declaration: &TokenStream,
+    lifetime: &TokenStream,
+) -> TokenStream {
+    let assoc = proc_macro2::Ident::new(assoc, proc_macro2::Span::call_site());
+    if is_async {
+        return quote! {
+            <#declaration as ::locktree::#lock>::#assoc<#lifetime>
+        };
+    }
+
+    let alias = match assoc.to_string().as_str() {
+        "Guard" => "PluggedMutexGuard",
+        "ReadGuard" => "PluggedRwLockReadGuard",
+        "WriteGuard" => "PluggedRwLockWriteGuard",
+        "UpgradableGuard" => "PluggedRwLockUpgradableReadGuard",
+        _ => unreachable!(),
+    };
+    let alias = proc_macro2::Ident::new(alias, proc_macro2::Span::call_site());
+
+    quote! {
+        ::locktree::#alias<#lifetime, #declaration>
+    }
+}
+
+/// Identifier of the field or function named after a lock, which must be raw
+/// if the lock's name is a keyword.
+fn field_ident(name: &str) -> proc_macro2::Ident {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        proc_macro2::Ident::new(name, proc_macro2::Span::call_site())
+    } else {
+        proc_macro2::Ident::new_raw(name, proc_macro2::Span::call_site())
+    }
+}
+
 fn snake_to_camel_case(x: &str) -> String {
     let mut camel = String::new();
     for word in x.split('_') {
-        camel.extend(word.chars().next().unwrap().to_uppercase());
-        camel.push_str(&word[1..])
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
     }
 
     camel
 }
+
+/// Report attributes whose name is not in `allowed`.
+fn check_attributes(
+    attrs: &[Attribute],
+    allowed: &[&str],
+    errors: &mut Errors,
+) {
+    for attr in attrs {
+        if !allowed.iter().any(|x| attr.path.is_ident(x)) {
+            errors.push(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "unsupported attribute, expected one of: {}",
+                    allowed
+                        .iter()
+                        .map(|x| format!("`{}`", x))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            ));
+        }
+    }
+}
+
+/// Filter `attrs` down to the ones named in `names`.
+fn select_attributes<'a>(
+    attrs: &'a [Attribute],
+    names: &'a [&str],
+) -> impl Iterator<Item = &'a Attribute> + 'a {
+    attrs
+        .iter()
+        .filter(move |attr| names.iter().any(|x| attr.path.is_ident(x)))
+}
+
+/// Skip everything up to and including the next `,` outside of angle
+/// brackets, so parsing can resume after an invalid lock without stopping
+/// inside its generic arguments.
+fn skip_past_comma(input: ParseStream) {
+    let mut depth = 0usize;
+    let mut after_dash = false;
+    while !input.is_empty() && (depth > 0 || !input.peek(Token![,])) {
+        let token = input.parse::<proc_macro2::TokenTree>().unwrap();
+        let punct = match &token {
+            proc_macro2::TokenTree::Punct(punct) => Some(punct.as_char()),
+            _ => None,
+        };
+        match punct {
+            Some('<') => depth += 1,
+            // The `>` of `->` doesn't close an angle bracket.
+            Some('>') if !after_dash => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        after_dash = punct == Some('-');
+    }
+    if !input.is_empty() {
+        input.parse::<Token![,]>().unwrap();
+    }
+}
+
+/// Accumulates errors so they can all be reported at once.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn finish<T>(self, value: T) -> Result<T> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(value),
+        }
+    }
+}
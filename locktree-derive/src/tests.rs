@@ -1,4 +1,4 @@
-use crate::locktree_impl;
+use crate::{backend_impl, lock_many_impl, locktree_impl, Backend, LockTree};
 use pretty_assertions::assert_eq;
 use proc_macro2::TokenStream;
 
@@ -15,10 +15,33 @@ fn should_output_barebones_with_empty_struct() {
         struct MainLockTree {}
 
         impl MainLockTree {
-            pub fn new() -> Self {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new() -> Self {
                 Self {}
             }
         }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {}
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
         ",
     );
 }
@@ -37,12 +60,14 @@ fn should_handle_a_single_mutex() {
         }
 
         impl MainLockTree {
-            pub fn new(mutex_value: ()) -> Self {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex_value: ()) -> Self {
                 Self {
                     mutex: ::locktree::New::new(mutex_value),
                 }
             }
 
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
             pub fn lock_mutex<'a>(
                 &'a self
             ) -> (
@@ -51,13 +76,291 @@ fn should_handle_a_single_mutex() {
             ) {
                 (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
             }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
         }
 
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
         struct MainLockTreeMutex<'b> {
             locks: &'b MainLockTree
         }
 
-        impl<'b> MainLockTreeMutex<'b> {}
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
         ",
     );
 }
@@ -76,12 +379,14 @@ fn should_handle_a_mutex_with_an_explicit_hkt() {
         }
 
         impl MainLockTree {
-            pub fn new(mutex_value: ()) -> Self {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex_value: ()) -> Self {
                 Self {
                     mutex: ::locktree::New::new(mutex_value),
                 }
             }
 
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
             pub fn lock_mutex<'a>(
                 &'a self
             ) -> (
@@ -90,13 +395,291 @@ fn should_handle_a_mutex_with_an_explicit_hkt() {
             ) {
                 (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
             }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                f: F,
+            ) -> <SuperMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> SuperMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                <SuperMutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>
+            >
+            where
+                for<'x> SuperMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperMutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
         }
 
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
         struct MainLockTreeMutex<'b> {
             locks: &'b MainLockTree
         }
 
-        impl<'b> MainLockTreeMutex<'b> {}
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, SuperMutex<()>>,
+                f: F,
+            ) -> <SuperMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> SuperMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                <SuperMutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, SuperMutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, SuperMutex<()>>
+            >
+            where
+                for<'x> SuperMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperMutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, SuperMutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, SuperMutex<()>>,
+                f: F,
+            ) -> <SuperMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> SuperMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperMutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                <SuperMutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, SuperMutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, SuperMutex<()>>
+            >
+            where
+                for<'x> SuperMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperMutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperMutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
         ",
     );
 }
@@ -115,252 +698,12738 @@ fn should_handle_an_async_mutex() {
         }
 
         impl MainLockTree {
-            pub fn new(mutex_value: ()) -> Self {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex_value: ()) -> Self {
                 Self {
                     mutex: ::locktree::New::new(mutex_value),
                 }
             }
 
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
             pub fn lock_mutex<'a>(
                 &'a self
             ) -> (
-                ::locktree::PluggedAsyncMutexGuard<'a, SuperMutex<()>>,
+                impl ::std::future::Future<Output = <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>> + 'a,
                 MainLockTreeMutex<'a>
             ) {
                 (::locktree::AsyncMutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
             }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::AsyncMutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeMutex<'a>
+            )
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncTimedMutex,
+            {
+                (::locktree::AsyncTimedMutex::lock_for(&self.mutex, timeout), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeMutex<'a>
+            )
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncTimedMutex,
+            {
+                (::locktree::AsyncTimedMutex::lock_until(&self.mutex, deadline), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeMutexDeadline<'a>
+            )
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncTimedMutex,
+            {
+                (
+                    ::locktree::deadline::lock_async(
+                        \"mutex\",
+                        ::locktree::AsyncTimedMutex::lock_until(&self.mutex, deadline)
+                    ),
+                    MainLockTreeMutexDeadline {
+                        locks: self,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                f: F,
+            ) -> <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncMappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::Target) -> &'x mut U,
+            {
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::MappedGuard<'a, U>,
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>
+            >
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncMappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::try_map(guard, f)
+            }
         }
 
-        struct MainLockTreeMutex<'b> {
-            locks: &'b MainLockTree
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
         }
 
-        impl<'b> MainLockTreeMutex<'b> {}
-        ",
-    );
-}
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeMutexDeadline<'a>
+            )
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncTimedMutex,
+            {
+                let deadline = self.deadline;
+                (
+                    ::locktree::deadline::lock_async(
+                        \"mutex\",
+                        ::locktree::AsyncTimedMutex::lock_until(&self.locks.mutex, deadline)
+                    ),
+                    MainLockTreeMutexDeadline {
+                        locks: self.locks,
+                        deadline: deadline
+                    },
+                )
+            }
 
-#[test]
-fn should_handle_a_single_rw_lock() {
-    compare_input_output(
-        "
-        Main {
-            rw_lock: RwLock<()>
-        }
-        ",
-        "
-        struct MainLockTree {
-            rw_lock: ::std::sync::RwLock<()>,
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::AsyncMutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
         }
 
-        impl MainLockTree {
-            pub fn new(rw_lock_value: ()) -> Self {
-                Self {
-                    rw_lock: ::locktree::New::new(rw_lock_value),
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
                 }
             }
+        }
 
-            pub fn read_rw_lock<'a>(
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
                 &'a self
-            ) -> (
-                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
-                MainLockTreeRwLock<'a>
-            ) {
-                (::locktree::RwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncMutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
             }
 
-            pub fn write_rw_lock<'a>(
-                &'a self
-            ) -> (
-                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
-                MainLockTreeRwLock<'a>
-            ) {
-                (::locktree::RwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'b>,
+                f: F,
+            ) -> <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncMappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::Target) -> &'x mut U,
+            {
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::MappedGuard<'b, U>,
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'b>
+            >
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncMappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::try_map(guard, f)
             }
         }
 
-        struct MainLockTreeRwLock<'b> {
-            locks: &'b MainLockTree
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
         }
 
-        impl<'b> MainLockTreeRwLock<'b> {}
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncMutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'b>,
+                f: F,
+            ) -> <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncMappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::MappedGuard<'b, U>,
+                <SuperMutex<()> as ::locktree::AsyncMutex> ::Guard<'b>
+            >
+            where
+                for<'x> SuperMutex<()> : ::locktree::AsyncMappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperMutex<()> as ::locktree::AsyncMappableMutex> ::try_map(guard, f)
+            }
+        }
         ",
     );
 }
 
 #[test]
-fn should_handle_an_rw_lock_with_an_explicit_hkt() {
+fn should_handle_a_single_rw_lock() {
     compare_input_output(
         "
         Main {
-            rw_lock: RwLock(SuperRwLock)<()>
+            rw_lock: RwLock<()>
         }
         ",
         "
         struct MainLockTree {
-            rw_lock: SuperRwLock<()>,
+            rw_lock: ::std::sync::RwLock<()>,
         }
 
         impl MainLockTree {
-            pub fn new(rw_lock_value: ()) -> Self {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(rw_lock_value: ()) -> Self {
                 Self {
                     rw_lock: ::locktree::New::new(rw_lock_value),
                 }
             }
 
+            #[doc = \"Locks `rw_lock` for reading. Returns its guard along with a forward locktree to take the next locks.\"]
             pub fn read_rw_lock<'a>(
                 &'a self
             ) -> (
-                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
                 MainLockTreeRwLock<'a>
             ) {
                 (::locktree::RwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
             }
 
+            #[doc = \"Locks `rw_lock` for writing. Returns its guard along with a forward locktree to take the next locks.\"]
             pub fn write_rw_lock<'a>(
                 &'a self
             ) -> (
-                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
                 MainLockTreeRwLock<'a>
             ) {
                 (::locktree::RwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
             }
-        }
-
-        struct MainLockTreeRwLock<'b> {
-            locks: &'b MainLockTree
-        }
-
-        impl<'b> MainLockTreeRwLock<'b> {}
-        ",
-    );
-}
-
-#[test]
-fn should_handle_an_async_rw_lock() {
-    compare_input_output(
-        "
-        Main {
-            rw_lock: async RwLock(SuperRwLock)<()>
-        }
-        ",
-        "
-        struct MainLockTree {
-            rw_lock: SuperRwLock<()>,
-        }
 
-        impl MainLockTree {
-            pub fn new(rw_lock_value: ()) -> Self {
-                Self {
-                    rw_lock: ::locktree::New::new(rw_lock_value),
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::RwLock::try_read(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
                 }
             }
 
-            pub fn read_rw_lock<'a>(
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
                 &'a self
-            ) -> (
-                ::locktree::PluggedAsyncRwLockReadGuard<'a, SuperRwLock<()>>,
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
                 MainLockTreeRwLock<'a>
-            ) {
-                (::locktree::AsyncRwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            )> {
+                match ::locktree::RwLock::try_write(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
             }
 
-            pub fn write_rw_lock<'a>(
-                &'a self
-            ) -> (
-                ::locktree::PluggedAsyncRwLockWriteGuard<'a, SuperRwLock<()>>,
+            #[doc = \"Locks `rw_lock` for reading, giving up after `timeout`.\"]
+            pub fn read_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
                 MainLockTreeRwLock<'a>
-            ) {
-                (::locktree::AsyncRwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::read_for(&self.rw_lock, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
             }
-        }
 
+            #[doc = \"Locks `rw_lock` for writing, giving up after `timeout`.\"]
+            pub fn write_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::write_for(&self.rw_lock, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`.\"]
+            pub fn read_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::read_until(&self.rw_lock, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`.\"]
+            pub fn write_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::write_until(&self.rw_lock, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn read_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::read_until(&self.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn write_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::write_until(&self.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'a, U>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x U,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'a, U>,
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>
+            >
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'a, U>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'a, U>,
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>
+            >
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `rw_lock` for reading, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::read_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::write_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::RwLock::try_read(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::RwLock::try_write(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`. It can't take any other lock.\"]
         struct MainLockTreeRwLock<'b> {
             locks: &'b MainLockTree
         }
 
-        impl<'b> MainLockTreeRwLock<'b> {}
+        impl<'b> MainLockTreeRwLock<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>,
+            ) -> ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::DowngradableRwLock,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::DowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x U,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>,
+                ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>
+            >
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>,
+                ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>
+            >
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeRwLockDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRwLockDeadline<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockReadGuard<'a, ::std::sync::RwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockWriteGuard<'a, ::std::sync::RwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>,
+            ) -> ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::DowngradableRwLock,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::DowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> &'x U,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>,
+                ::locktree::PluggedRwLockReadGuard<'b, ::std::sync::RwLock<()>>
+            >
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x U>,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>,
+                ::locktree::PluggedRwLockWriteGuard<'b, ::std::sync::RwLock<()>>
+            >
+            where
+                for<'x> ::std::sync::RwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::RwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
         ",
     );
 }
 
 #[test]
-fn should_handle_two_locks() {
+fn should_handle_an_rw_lock_with_an_explicit_hkt() {
     compare_input_output(
         "
         Main {
-            mutex0: Mutex<()>,
-            mutex1: Mutex<()>,
+            rw_lock: RwLock(SuperRwLock)<()>
         }
         ",
         "
         struct MainLockTree {
-            mutex0: ::std::sync::Mutex<()>,
-            mutex1: ::std::sync::Mutex<()>,
+            rw_lock: SuperRwLock<()>,
         }
 
         impl MainLockTree {
-            pub fn new(mutex0_value: (), mutex1_value: ()) -> Self {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(rw_lock_value: ()) -> Self {
                 Self {
-                    mutex0: ::locktree::New::new(mutex0_value),
-                    mutex1: ::locktree::New::new(mutex1_value),
+                    rw_lock: ::locktree::New::new(rw_lock_value),
                 }
             }
 
-            pub fn lock_mutex0<'a>(
+            #[doc = \"Locks `rw_lock` for reading. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn read_rw_lock<'a>(
                 &'a self
             ) -> (
-                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
-                MainLockTreeMutex0<'a>
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
             ) {
-                (::locktree::Mutex::lock(&self.mutex0), MainLockTreeMutex0 { locks: self })
+                (::locktree::RwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
             }
 
-            pub fn lock_mutex1<'a>(
+            #[doc = \"Locks `rw_lock` for writing. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn write_rw_lock<'a>(
                 &'a self
             ) -> (
-                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
-                MainLockTreeMutex1<'a>
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
             ) {
-                (::locktree::Mutex::lock(&self.mutex1), MainLockTreeMutex1 { locks: self })
+                (::locktree::RwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::RwLock::try_read(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::RwLock::try_write(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up after `timeout`.\"]
+            pub fn read_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::read_for(&self.rw_lock, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up after `timeout`.\"]
+            pub fn write_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::write_for(&self.rw_lock, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`.\"]
+            pub fn read_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::read_until(&self.rw_lock, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`.\"]
+            pub fn write_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::write_until(&self.rw_lock, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn read_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::read_until(&self.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn write_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::write_until(&self.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'a, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'a, U>,
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'a, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'a, U>,
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
             }
         }
 
-        struct MainLockTreeMutex0<'b> {
-            locks: &'b MainLockTree
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
         }
 
-        impl<'b> MainLockTreeMutex0<'b> {
-            pub fn lock_mutex1<'a>(
-                &'a mut self
-            ) -> (
-                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
-                MainLockTreeMutex1<'a>
-            ) {
-                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `rw_lock` for reading, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::read_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::write_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::RwLock::try_read(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::RwLock::try_write(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
             }
         }
 
-        struct MainLockTreeMutex1<'b> {
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`. It can't take any other lock.\"]
+        struct MainLockTreeRwLock<'b> {
             locks: &'b MainLockTree
         }
 
-        impl<'b> MainLockTreeMutex1<'b> {}
+        impl<'b> MainLockTreeRwLock<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+            ) -> ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::DowngradableRwLock,
+            {
+                <SuperRwLock<()> as ::locktree::DowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>,
+                ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>,
+                ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeRwLockDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRwLockDeadline<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+            ) -> ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::DowngradableRwLock,
+            {
+                <SuperRwLock<()> as ::locktree::DowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>,
+                ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>,
+                ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
         ",
     );
 }
 
-fn compare_input_output(input: &str, output: &str) {
-    assert_eq!(
-        locktree_impl(syn::parse_str(input).unwrap())
-            .to_string()
-            .replace(" '", "'")
-            .replace(" ,", ",")
-            .replace(" >", ">"),
-        syn::parse_str::<TokenStream>(output)
-            .unwrap()
-            .to_string()
-            .replace(" ,", ",")
-            .replace(" >", ">")
-    );
+#[test]
+fn should_handle_an_async_rw_lock() {
+    compare_input_output(
+        "
+        Main {
+            rw_lock: async RwLock(SuperRwLock)<()>
+        }
+        ",
+        "
+        struct MainLockTree {
+            rw_lock: SuperRwLock<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(rw_lock_value: ()) -> Self {
+                Self {
+                    rw_lock: ::locktree::New::new(rw_lock_value),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<Output = <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>> + 'a,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::AsyncRwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<Output = <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>> + 'a,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::AsyncRwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_read(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_write(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up after `timeout`.\"]
+            pub fn read_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::read_for(&self.rw_lock, timeout), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up after `timeout`.\"]
+            pub fn write_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::write_for(&self.rw_lock, timeout), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`.\"]
+            pub fn read_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::read_until(&self.rw_lock, deadline), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`.\"]
+            pub fn write_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::write_until(&self.rw_lock, deadline), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn read_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeRwLockDeadline<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::read_until(&self.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn write_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeRwLockDeadline<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::write_until(&self.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'a, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'a, U>,
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'a, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'a, U>,
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `rw_lock` for reading, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeRwLockDeadline<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                let deadline = self.deadline;
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::read_until(&self.locks.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self.locks,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeRwLockDeadline<'a>
+            )
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                let deadline = self.deadline;
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::write_until(&self.locks.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self.locks,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_read(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_write(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`. It can't take any other lock.\"]
+        struct MainLockTreeRwLock<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeRwLock<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncDowngradableRwLock,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncDowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'b, U>,
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'b, U>,
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeRwLockDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRwLockDeadline<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncDowngradableRwLock,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncDowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target
+                ) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'b, U>,
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target
+                ) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'b, U>,
+                <SuperRwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_handle_an_upgradable_rw_lock() {
+    compare_input_output(
+        "
+        Main {
+            rw_lock: UpgradableRwLock(SuperRwLock)<()>
+        }
+        ",
+        "
+        struct MainLockTree {
+            rw_lock: SuperRwLock<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(rw_lock_value: ()) -> Self {
+                Self {
+                    rw_lock: ::locktree::New::new(rw_lock_value),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::RwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::RwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::RwLock::try_read(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::RwLock::try_write(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up after `timeout`.\"]
+            pub fn read_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::read_for(&self.rw_lock, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up after `timeout`.\"]
+            pub fn write_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::write_for(&self.rw_lock, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`.\"]
+            pub fn read_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::read_until(&self.rw_lock, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`.\"]
+            pub fn write_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::RwLock::write_until(&self.rw_lock, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn read_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::read_until(&self.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn write_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::write_until(&self.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for an upgradable read. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn upgradable_read_rw_lock<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedRwLockUpgradableReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::UpgradableRwLock::upgradable_read(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for an upgradable read without waiting.\"]
+            pub fn try_upgradable_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockUpgradableReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::UpgradableRwLock::try_upgradable_read(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'a, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'a, U>,
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'a, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'a, U>,
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `rw_lock` for reading, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::read_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::RwLock::write_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::RwLock::try_read(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::RwLock::try_write(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for an upgradable read, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn upgradable_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedRwLockUpgradableReadGuard<'a, SuperRwLock<()>>,
+                    MainLockTreeRwLockDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"rw_lock\",
+                    ::locktree::UpgradableRwLock::upgradable_read_until(&self.locks.rw_lock, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for an upgradable read without waiting.\"]
+            pub fn try_upgradable_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedRwLockUpgradableReadGuard<'a, SuperRwLock<()>>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::UpgradableRwLock::try_upgradable_read(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`. It can't take any other lock.\"]
+        struct MainLockTreeRwLock<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeRwLock<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+            ) -> ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::DowngradableRwLock,
+            {
+                <SuperRwLock<()> as ::locktree::DowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Upgrades an upgradable read guard of `rw_lock` to a write guard.\"]
+            pub fn upgrade_rw_lock(
+                &mut self,
+                guard: ::locktree::PluggedRwLockUpgradableReadGuard<'b, SuperRwLock<()>>,
+            ) -> ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>> {
+                <SuperRwLock<()> as ::locktree::UpgradableRwLock> ::upgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>,
+                ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>,
+                ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeRwLockDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRwLockDeadline<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockReadGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedRwLockWriteGuard<'a, SuperRwLock<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::RwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+            ) -> ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::DowngradableRwLock,
+            {
+                <SuperRwLock<()> as ::locktree::DowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> &'x U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedReadGuard<'b, U>,
+                ::locktree::PluggedRwLockReadGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> &'x mut U,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::MappedWriteGuard<'b, U>,
+                ::locktree::PluggedRwLockWriteGuard<'b, SuperRwLock<()>>
+            >
+            where
+                for<'x> SuperRwLock<()> : ::locktree::MappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut <SuperRwLock<()> as ::locktree::MappableRwLock> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                <SuperRwLock<()> as ::locktree::MappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_upgradable_rw_locks_without_an_hkt() {
+    compare_input_errors(
+        "
+        Main {
+            rw_lock: UpgradableRwLock<()>
+        }
+        ",
+        &["`UpgradableRwLock` locks must have an explicit HKT"],
+    );
+}
+
+#[test]
+fn should_handle_a_nested_tree() {
+    compare_input_output(
+        "
+        Main {
+            storage: Tree<StorageLockTree>,
+            mutex: Mutex<()>,
+        }
+        ",
+        "
+        struct MainLockTree {
+            storage: StorageLockTree,
+            mutex: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(storage_value: StorageLockTree, mutex_value: ()) -> Self {
+                Self {
+                    storage: storage_value,
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+
+            #[doc = \"Enters the nested tree `storage`. It is then reached through the returned forward locktree.\"]
+            pub fn enter_storage<'a>(&'a self) -> MainLockTreeStorage<'a> {
+                MainLockTreeStorage { locks: self }
+            }
+
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Enters the nested tree `storage`. It is then reached through the returned forward locktree.\"]
+            pub fn enter_storage<'a>(&'a self) -> MainLockTreeStorageDeadline<'a> {
+                MainLockTreeStorageDeadline {
+                    locks: self.locks,
+                    deadline: self.deadline
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after entering `storage`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex`\"]
+        struct MainLockTreeStorage<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeStorage<'b> {
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex), MainLockTreeMutex { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex), MainLockTreeMutex { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_mutex`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Nested tree `storage`. Its guards keep this forward locktree borrowed.\"]
+            pub fn storage(&mut self) -> &StorageLockTree {
+                &self.locks.storage
+            }
+        }
+
+        #[doc = \"Forward locktree after entering `storage`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex`\"]
+        struct MainLockTreeStorageDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeStorageDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_mutex`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Nested tree `storage`, giving up at the deadline of this forward locktree. Its guards keep this forward locktree borrowed.\"]
+            pub fn storage(&mut self) -> <StorageLockTree as ::locktree::WithDeadline> ::Deadline<'_>
+            where
+                for<'x> StorageLockTree: ::locktree::WithDeadline,
+            {
+                ::locktree::WithDeadline::with_deadline(&self.locks.storage, self.deadline,)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_invalid_nested_trees() {
+    compare_input_errors(
+        "
+        Main {
+            tree0: async Tree<StorageLockTree>,
+            tree1: Tree<StorageLockTree, CacheLockTree>,
+        }
+        ",
+        &[
+            "nested trees can't be async or have an explicit HKT",
+            "expected a single lock tree type",
+        ],
+    );
+}
+
+#[test]
+fn should_handle_a_shared_field() {
+    compare_input_output(
+        "
+        Main {
+            config: Shared<Config>,
+            mutex: Mutex<()>,
+        }
+        ",
+        "
+        struct MainLockTree {
+            config: Config,
+            mutex: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(config_value: Config, mutex_value: ()) -> Self {
+                Self {
+                    config: config_value,
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &Config {
+                &self.config
+            }
+
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &Config {
+                &self.locks.config
+            }
+
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &'b Config {
+                &self.locks.config
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &'b Config {
+                &self.locks.config
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_handle_default_values() {
+    compare_input_output(
+        "
+        #[builder]
+        Main {
+            config: Shared<Config> = (Config { limit: 16 }),
+            mutex: Mutex<()> = (),
+        }
+        ",
+        "
+        struct MainLockTree {
+            config: Config,
+            mutex: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(config_value: Config, mutex_value: ()) -> Self {
+                Self {
+                    config: config_value,
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+
+            #[doc = \"Starts building the locktree with `MainLockTreeBuilder`.\"]
+            fn builder() -> MainLockTreeBuilder {
+                MainLockTreeBuilder {
+                    config: ::std::option::Option::None,
+                    mutex: ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &Config {
+                &self.config
+            }
+
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        impl ::std::default::Default for MainLockTree {
+            fn default() -> Self {
+                let config_value: Config = Config { limit: 16 };
+                let mutex_value: () = ();
+                Self {
+                    config: config_value,
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+        }
+
+        #[doc = \"Builder of `MainLockTree`, taking the initial value of each lock.\"]
+        struct MainLockTreeBuilder {
+            config: ::std::option::Option<Config>,
+            mutex: ::std::option::Option<()>,
+        }
+
+        #[allow(dead_code)]
+        impl MainLockTreeBuilder {
+            #[doc = \"Sets the initial value of `config`.\"]
+            pub fn config(mut self, value: Config) -> Self {
+                self.config = ::std::option::Option::Some(value);
+                self
+            }
+
+            #[doc = \"Sets the initial value of `mutex`.\"]
+            pub fn mutex(mut self, value: ()) -> Self {
+                self.mutex = ::std::option::Option::Some(value);
+                self
+            }
+
+            #[doc = \"Builds the locktree. Locks that weren't set take their default value, and `MissingValue` is returned for those without one.\"]
+            fn build(
+                self
+            ) -> ::std::result::Result<MainLockTree, ::locktree::MissingValue> {
+                let config_value = match self.config {
+                    ::std::option::Option::Some(value) => value,
+                    ::std::option::Option::None => Config { limit: 16 },
+                };
+                let mutex_value = match self.mutex {
+                    ::std::option::Option::Some(value) => value,
+                    ::std::option::Option::None => (),
+                };
+                ::std::result::Result::Ok(MainLockTree {
+                    config: config_value,
+                    mutex: ::locktree::New::new(mutex_value),
+                })
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &Config {
+                &self.locks.config
+            }
+
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &'b Config {
+                &self.locks.config
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &'b Config {
+                &self.locks.config
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_return_missing_builder_values() {
+    compare_input_output(
+        "
+        #[builder]
+        Main {
+            config: Shared<Config>,
+        }
+        ",
+        "
+        struct MainLockTree {
+            config: Config,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(config_value: Config) -> Self {
+                Self {
+                    config: config_value,
+                }
+            }
+
+            #[doc = \"Starts building the locktree with `MainLockTreeBuilder`.\"]
+            fn builder() -> MainLockTreeBuilder {
+                MainLockTreeBuilder {
+                    config: ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &Config {
+                &self.config
+            }
+        }
+
+        #[doc = \"Builder of `MainLockTree`, taking the initial value of each lock.\"]
+        struct MainLockTreeBuilder {
+            config: ::std::option::Option<Config>,
+        }
+
+        #[allow(dead_code)]
+        impl MainLockTreeBuilder {
+            #[doc = \"Sets the initial value of `config`.\"]
+            pub fn config(mut self, value: Config) -> Self {
+                self.config = ::std::option::Option::Some(value);
+                self
+            }
+
+            #[doc = \"Builds the locktree. Locks that weren't set take their default value, and `MissingValue` is returned for those without one.\"]
+            fn build(
+                self
+            ) -> ::std::result::Result<MainLockTree, ::locktree::MissingValue> {
+                let config_value = match self.config {
+                    ::std::option::Option::Some(value) => value,
+                    ::std::option::Option::None => return ::std::result::Result::Err(
+                        ::locktree::MissingValue::new(\"config\")
+                    ),
+                };
+                ::std::result::Result::Ok(MainLockTree {
+                    config: config_value,
+                })
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Shared field `config`.\"]
+            pub fn config(&self) -> &Config {
+                &self.locks.config
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_invalid_construction() {
+    compare_input_errors(
+        "
+        #[builder(all)]
+        #[derive(Default)]
+        Main {
+            mutex: Mutex<()> = (),
+        }
+
+        #[builder]
+        #[builder]
+        Partial {
+            mutex: Mutex<()>,
+        }
+
+        Unused {
+            mutex: Mutex<()> = (),
+            rw_lock: RwLock<()>,
+        }
+        ",
+        &[
+            "expected `#[builder]`",
+            "`Default` is already implemented by `locktree!` since every \
+             lock has a default value",
+            "duplicate `builder` attribute",
+            "default values are only used by `#[builder]`, or by `Default` \
+             when every lock has one",
+        ],
+    );
+}
+
+#[test]
+fn should_report_invalid_shared_fields() {
+    compare_input_errors(
+        "
+        Main {
+            config0: async Shared<Config>,
+            config1: Shared<Config, Limits>,
+            config2: Shared<Config> {
+                mutex: Mutex<()>,
+            },
+        }
+        ",
+        &[
+            "shared fields can't be async or have an explicit HKT",
+            "expected a single type",
+            "shared fields can't have branches",
+        ],
+    );
+}
+
+#[test]
+fn should_handle_two_locks() {
+    compare_input_output(
+        "
+        Main {
+            mutex0: Mutex<()>,
+            mutex1: Mutex<()>,
+        }
+        ",
+        "
+        struct MainLockTree {
+            mutex0: ::std::sync::Mutex<()>,
+            mutex1: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex0_value: (), mutex1_value: ()) -> Self {
+                Self {
+                    mutex0: ::locktree::New::new(mutex0_value),
+                    mutex1: ::locktree::New::new(mutex1_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex0<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex0), MainLockTreeMutex0 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up after `timeout`.\"]
+            pub fn lock_mutex0_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex0, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`.\"]
+            pub fn lock_mutex0_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex0, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex0_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex1<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex1), MainLockTreeMutex1 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            pub fn lock_mutex1_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            pub fn lock_mutex1_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex1_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex0`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex1`\"]
+        struct MainLockTreeMutex0<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex0<'b> {
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            pub fn lock_mutex1_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            pub fn lock_mutex1_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex1`\"]
+        struct MainLockTreeMutex0Deadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutex0Deadline<'b> {
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`. It can't take any other lock.\"]
+        struct MainLockTreeMutex1<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex1<'b> {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutex1Deadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutex1Deadline<'b> {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_handle_an_exclusive_tree() {
+    compare_input_output(
+        "
+        exclusive Main {
+            mutex0: Mutex<()>,
+            mutex1: Mutex<()>,
+        }
+        ",
+        "
+        struct MainLockTree {
+            mutex0: ::std::sync::Mutex<()>,
+            mutex1: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex0_value: (), mutex1_value: ()) -> Self {
+                Self {
+                    mutex0: ::locktree::New::new(mutex0_value),
+                    mutex1: ::locktree::New::new(mutex1_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex0<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex0), MainLockTreeMutex0 { locks: self, token })
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex0 { locks: self, token }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up after `timeout`.\"]
+            pub fn lock_mutex0_for<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex0, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self, token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`.\"]
+            pub fn lock_mutex0_until<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex0, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self, token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex0_with_deadline<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self,
+                                token,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex1<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex1), MainLockTreeMutex1 { locks: self, token })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self, token }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            pub fn lock_mutex1_for<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self, token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            pub fn lock_mutex1_until<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self, token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex1_with_deadline<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self,
+                                token,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex0`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex0<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                token,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                token,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex1<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                token,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a self,
+                token: &'a mut ::locktree::LockToken
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                token,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex1`\"]
+        struct MainLockTreeMutex0<'b> {
+            locks: &'b MainLockTree,
+            token: &'b mut ::locktree::LockToken
+        }
+
+        impl<'b> MainLockTreeMutex0<'b> {
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ) {
+                (
+                    ::locktree::Mutex::lock(&self.locks.mutex1),
+                    MainLockTreeMutex1 { locks: self.locks, token: &mut *self.token }
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks, token: &mut *self.token }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            pub fn lock_mutex1_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks, token: &mut *self.token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            pub fn lock_mutex1_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks, token: &mut *self.token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ) {
+                (
+                    ::locktree::Mutex::lock(&self.locks.mutex1),
+                    MainLockTreeMutex1 { locks: self.locks, token: self.token }
+                )
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks, token: self.token }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks, token: self.token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks, token: self.token }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex1`\"]
+        struct MainLockTreeMutex0Deadline<'b> {
+            locks: &'b MainLockTree,
+            token: &'b mut ::locktree::LockToken,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutex0Deadline<'b> {
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                token: &mut *self.token,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                token: &mut *self.token,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                token: self.token,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                token: self.token,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`. It can't take any other lock.\"]
+        struct MainLockTreeMutex1<'b> {
+            locks: &'b MainLockTree,
+            token: &'b mut ::locktree::LockToken
+        }
+
+        impl<'b> MainLockTreeMutex1<'b> {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutex1Deadline<'b> {
+            locks: &'b MainLockTree,
+            token: &'b mut ::locktree::LockToken,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutex1Deadline<'b> {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_apply_visibility_to_generated_items() {
+    compare_input_output(
+        "
+        pub(crate) Main {
+            mutex: Mutex<()>
+        }
+        ",
+        "
+        pub(crate) struct MainLockTree {
+            mutex: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            pub(crate) fn new(mutex_value: ()) -> Self {
+                Self {
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        pub(crate) struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        pub(crate) struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        pub(crate) struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_handle_leading_and_doubled_underscores() {
+    compare_input_output(
+        "
+        Main {
+            _mutex__0: Mutex<()>
+        }
+        ",
+        "
+        struct MainLockTree {
+            _mutex__0: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(_mutex__0_value: ()) -> Self {
+                Self {
+                    _mutex__0: ::locktree::New::new(_mutex__0_value),
+                }
+            }
+
+            #[doc = \"Locks `_mutex__0`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock__mutex__0<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ) {
+                (::locktree::Mutex::lock(&self._mutex__0), MainLockTreeMutex0 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `_mutex__0` without waiting.\"]
+            pub fn try_lock__mutex__0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self._mutex__0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `_mutex__0`, giving up after `timeout`.\"]
+            pub fn lock__mutex__0_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self._mutex__0, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `_mutex__0`, giving up at `deadline`.\"]
+            pub fn lock__mutex__0_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self._mutex__0, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `_mutex__0`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock__mutex__0_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"_mutex__0\",
+                    ::locktree::Mutex::lock_until(&self._mutex__0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `_mutex__0` to a part of the locked data.\"]
+            pub fn map__mutex__0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map__mutex__0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map__mutex__0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `_mutex__0`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock__mutex__0<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"_mutex__0\",
+                    ::locktree::Mutex::lock_until(&self.locks._mutex__0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `_mutex__0` without waiting.\"]
+            pub fn try_lock__mutex__0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks._mutex__0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `_mutex__0`. It can't take any other lock.\"]
+        struct MainLockTreeMutex0<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex0<'b> {
+            #[doc = \"Tries to lock `_mutex__0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock__mutex__0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks._mutex__0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `_mutex__0` to a part of the locked data.\"]
+            pub fn map__mutex__0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map__mutex__0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map__mutex__0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `_mutex__0`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutex0Deadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutex0Deadline<'b> {
+            #[doc = \"Tries to lock `_mutex__0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock__mutex__0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks._mutex__0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `_mutex__0` to a part of the locked data.\"]
+            pub fn map__mutex__0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map__mutex__0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map__mutex__0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_handle_generics() {
+    compare_input_output(
+        "
+        Main<'c, T: Clone, const N: usize> where T: 'static {
+            mutex0: Mutex<[&'c T; N]>,
+            mutex1: Mutex<()>,
+        }
+        ",
+        "
+        struct MainLockTree<'c, T: Clone, const N: usize> where T: 'static {
+            mutex0: ::std::sync::Mutex<[&'c T; N]>,
+            mutex1: ::std::sync::Mutex<()>,
+        }
+
+        impl<'c, T: Clone, const N: usize> MainLockTree<'c, T, N> where T: 'static {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex0_value: [&'c T; N], mutex1_value: ()) -> Self {
+                Self {
+                    mutex0: ::locktree::New::new(mutex0_value),
+                    mutex1: ::locktree::New::new(mutex1_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex0<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                MainLockTreeMutex0<'a, 'c, T, N>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex0), MainLockTreeMutex0 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                MainLockTreeMutex0<'a, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up after `timeout`.\"]
+            pub fn lock_mutex0_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                MainLockTreeMutex0<'a, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex0, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`.\"]
+            pub fn lock_mutex0_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                MainLockTreeMutex0<'a, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex0, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex0_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                    MainLockTreeMutex0Deadline<'a, 'c, T, N>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<[&'c T; N]> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<[&'c T; N]> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex1<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex1), MainLockTreeMutex1 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            pub fn lock_mutex1_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            pub fn lock_mutex1_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex1_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a, 'c, T, N>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b, 'c, T: Clone, const N: usize>
+        where
+            T: 'static
+        {
+            locks: &'b MainLockTree<'c, T, N>,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b, 'c, T: Clone, const N: usize> MainLockTreeDeadline<'b, 'c, T, N>
+        where
+            T: 'static
+        {
+            #[doc = \"Locks `mutex0`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                    MainLockTreeMutex0Deadline<'a, 'c, T, N>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                MainLockTreeMutex0Deadline<'a, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a, 'c, T, N>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl<'c, T: Clone, const N: usize> ::locktree::WithDeadline for MainLockTree<'c, T, N>
+        where
+            T: 'static
+        {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b, 'c, T, N>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex1`\"]
+        struct MainLockTreeMutex0<'b, 'c, T: Clone, const N: usize> where T: 'static {
+            locks: &'b MainLockTree<'c, T, N>
+        }
+
+        impl<'b, 'c, T: Clone, const N: usize> MainLockTreeMutex0<'b, 'c, T, N>
+            where T: 'static
+        {
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            pub fn lock_mutex1_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            pub fn lock_mutex1_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b, 'c, T, N>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b, 'c, T, N>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<[&'c T; N]>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<[&'c T; N]> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<[&'c T; N]>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<[&'c T; N]>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<[&'c T; N]> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `mutex1`\"]
+        struct MainLockTreeMutex0Deadline<'b, 'c, T: Clone, const N: usize>
+        where
+            T: 'static
+        {
+            locks: &'b MainLockTree<'c, T, N>,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b, 'c, T: Clone, const N: usize> MainLockTreeMutex0Deadline<'b, 'c, T, N>
+        where
+            T: 'static
+        {
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a, 'c, T, N>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_lock_mutex1(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'b, 'c, T, N>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'b, 'c, T, N>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<[&'c T; N]>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<[&'c T; N]> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<[&'c T; N]>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<[&'c T; N]>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<[&'c T; N]> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<[&'c T; N]> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`. It can't take any other lock.\"]
+        struct MainLockTreeMutex1<'b, 'c, T: Clone, const N: usize> where T: 'static {
+            locks: &'b MainLockTree<'c, T, N>
+        }
+
+        impl<'b, 'c, T: Clone, const N: usize> MainLockTreeMutex1<'b, 'c, T, N>
+            where T: 'static
+        {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutex1Deadline<'b, 'c, T: Clone, const N: usize>
+        where
+            T: 'static
+        {
+            locks: &'b MainLockTree<'c, T, N>,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b, 'c, T: Clone, const N: usize> MainLockTreeMutex1Deadline<'b, 'c, T, N>
+        where
+            T: 'static
+        {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<[&'c T; N]>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_reserved_lifetimes() {
+    compare_input_errors(
+        "
+        Main<'a, 'b, 'c, 'x> {}
+        ",
+        &[
+            "`'a` is reserved for items generated by `locktree!`",
+            "`'b` is reserved for items generated by `locktree!`",
+            "`'x` is reserved for items generated by `locktree!`",
+        ],
+    );
+}
+
+#[test]
+fn should_forward_attributes() {
+    compare_input_output(
+        r#"
+        /// Main tree
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        Main {
+            /// First lock
+            mutex0: Mutex<()>,
+            #[cfg(feature = "mutex1")]
+            mutex1: Mutex<()>,
+        }
+        "#,
+        "
+        #[allow(dead_code)]
+        #[doc = \" Main tree\"]
+        #[derive(Debug)]
+        struct MainLockTree {
+            #[doc = \" First lock\"]
+            mutex0: ::std::sync::Mutex<()>,
+            #[cfg(feature = \"mutex1\")]
+            mutex1: ::std::sync::Mutex<()>,
+        }
+
+        #[allow(dead_code)]
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(
+                mutex0_value: (),
+                #[cfg(feature = \"mutex1\")]
+                mutex1_value: ()
+            ) -> Self {
+                Self {
+                    mutex0: ::locktree::New::new(mutex0_value),
+                    #[cfg(feature = \"mutex1\")]
+                    mutex1: ::locktree::New::new(mutex1_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`. Returns its guard along with a forward locktree to take the next locks.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn lock_mutex0<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex0), MainLockTreeMutex0 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up after `timeout`.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn lock_mutex0_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex0, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn lock_mutex0_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex0, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex0 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex0`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn lock_mutex0_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_map_mutex0<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex1), MainLockTreeMutex1 { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_map_mutex1<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        #[allow(dead_code)]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        #[allow(dead_code)]
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex0`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex0Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex0\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex0, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_lock_mutex0<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex0Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex0) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex0Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_lock_mutex1<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`.\"]
+        #[doc = \"\"]
+        #[cfg_attr(any(feature = \"mutex1\"), doc = \"It can still take:\")]
+        #[cfg_attr(not(any(feature = \"mutex1\")), doc = \"It can't take any other lock.\")]
+        #[cfg_attr(feature = \"mutex1\", doc = \"- `mutex1`\")]
+        #[allow(dead_code)]
+        struct MainLockTreeMutex0<'b> {
+            locks: &'b MainLockTree
+        }
+
+        #[allow(dead_code)]
+        impl<'b> MainLockTreeMutex0<'b> {
+            #[doc = \"Locks `mutex1`. Returns its guard along with a forward locktree to take the next locks.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up after `timeout`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex1`, giving up at `deadline`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn into_lock_mutex1(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.mutex1), MainLockTreeMutex1 { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_for`, but consumes this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn into_lock_mutex1_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.mutex1, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1_until`, but consumes this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn into_lock_mutex1_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex1 { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex0`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[cfg_attr(any(feature = \"mutex1\"), doc = \"It can still take:\")]
+        #[cfg_attr(not(any(feature = \"mutex1\")), doc = \"It can't take any other lock.\")]
+        #[cfg_attr(feature = \"mutex1\", doc = \"- `mutex1`\")]
+        #[allow(dead_code)]
+        struct MainLockTreeMutex0Deadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        #[allow(dead_code)]
+        impl<'b> MainLockTreeMutex0Deadline<'b> {
+            #[doc = \"Locks `mutex1`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_lock_mutex1<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_mutex1`, but consumes this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn into_lock_mutex1(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeMutex1Deadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex1\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex1, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_mutex1`, but consumes this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn into_try_lock_mutex1(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeMutex1Deadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex1) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutex1Deadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex0` to a part of the locked data.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex0`, but gives the guard back if `f` returns `None`.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_map_mutex0<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`. It can't take any other lock.\"]
+        #[allow(dead_code)]
+        #[cfg(feature = \"mutex1\")]
+        struct MainLockTreeMutex1<'b> {
+            locks: &'b MainLockTree
+        }
+
+        #[allow(dead_code)]
+        #[cfg(feature = \"mutex1\")]
+        impl<'b> MainLockTreeMutex1<'b> {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex1`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        #[allow(dead_code)]
+        #[cfg(feature = \"mutex1\")]
+        struct MainLockTreeMutex1Deadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        #[allow(dead_code)]
+        #[cfg(feature = \"mutex1\")]
+        impl<'b> MainLockTreeMutex1Deadline<'b> {
+            #[doc = \"Tries to lock `mutex0` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            #[doc = \"\"]
+            #[doc = \" First lock\"]
+            pub fn try_lock_mutex0_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex0),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `mutex1` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_lock_mutex1_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex1),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex1` to a part of the locked data.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex1`, but gives the guard back if `f` returns `None`.\"]
+            #[cfg(feature = \"mutex1\")]
+            pub fn try_map_mutex1<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_unsupported_attributes() {
+    compare_input_errors(
+        "
+        #[repr(C)]
+        Main {
+            #[allow(dead_code)]
+            mutex: Mutex<()>,
+        }
+        ",
+        &[
+            "unsupported attribute, expected one of: `allow`, `builder`, \
+             `cfg`, `derive`, `doc`, `poison`",
+            "unsupported attribute, expected one of: `cfg`, `doc`, `poison`",
+        ],
+    );
+}
+
+#[test]
+fn should_expand_parking_lot_shorthands() {
+    compare_input_output(
+        "
+        Main {
+            mutex: Mutex(parking_lot)<()>
+        }
+        ",
+        "
+        struct MainLockTree {
+            mutex: ::locktree::parking_lot::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex_value: ()) -> Self {
+                Self {
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                f: F,
+            ) -> < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::locktree::parking_lot::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>
+            >
+            where
+                for<'x> ::locktree::parking_lot::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::parking_lot::Mutex<()>>,
+                f: F,
+            ) -> < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::locktree::parking_lot::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::parking_lot::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::locktree::parking_lot::Mutex<()>>
+            >
+            where
+                for<'x> ::locktree::parking_lot::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::locktree::parking_lot::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::parking_lot::Mutex<()>>,
+                f: F,
+            ) -> < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::locktree::parking_lot::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::parking_lot::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::locktree::parking_lot::Mutex<()>>
+            >
+            where
+                for<'x> ::locktree::parking_lot::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::parking_lot::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_expand_async_shorthands() {
+    compare_input_output(
+        "
+        Main {
+            rw_lock: async RwLock(async_std)<()>
+        }
+        ",
+        "
+        struct MainLockTree {
+            rw_lock: ::locktree::async_std::sync::RwLock<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(rw_lock_value: ()) -> Self {
+                Self {
+                    rw_lock: ::locktree::New::new(rw_lock_value),
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<Output = < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>> + 'a,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::AsyncRwLock::read(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn write_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<Output = < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>> + 'a,
+                MainLockTreeRwLock<'a>
+            ) {
+                (::locktree::AsyncRwLock::write(&self.rw_lock), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_read(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                MainLockTreeRwLock<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_write(&self.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRwLock { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up after `timeout`.\"]
+            pub fn read_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::read_for(&self.rw_lock, timeout), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up after `timeout`.\"]
+            pub fn write_rw_lock_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::write_for(&self.rw_lock, timeout), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`.\"]
+            pub fn read_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::read_until(&self.rw_lock, deadline), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`.\"]
+            pub fn write_rw_lock_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<Output = ::std::result::Result< < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>, ::locktree::TimedOut>> + 'a,
+                MainLockTreeRwLock<'a>
+            )
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (::locktree::AsyncTimedRwLock::write_until(&self.rw_lock, deadline), MainLockTreeRwLock { locks: self })
+            }
+
+            #[doc = \"Locks `rw_lock` for reading, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn read_rw_lock_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<
+                                'a
+                            >,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeRwLockDeadline<'a>
+            )
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::read_until(&self.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]    pub fn write_rw_lock_with_deadline < 'a > (& 'a self , deadline : :: std :: time :: Instant) -> (impl :: std :: future :: Future < Output = :: std :: result :: Result < < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: WriteGuard < 'a > , :: locktree :: DeadlineExceeded > > + 'a , MainLockTreeRwLockDeadline < 'a >) where for < 'x > :: locktree :: async_std :: sync :: RwLock < () > : :: locktree :: AsyncTimedRwLock ,{
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::write_until(&self.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                f: F,
+            ) -> < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'a, U>
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x U,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<'a, U, F>(
+                &'a self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'a, U>,
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>
+            >
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                f: F,
+            ) -> < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'a, U>
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x mut U,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<'a, U, F>(
+                &'a self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'a, U>,
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>
+            >
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `rw_lock` for reading, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn read_rw_lock<'a>(
+                &'a self
+            ) -> (
+                impl ::std::future::Future<
+                        Output = ::std::result::Result<
+                            < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<
+                                'a
+                            >,
+                            ::locktree::DeadlineExceeded
+                        >
+                    > + 'a,
+                MainLockTreeRwLockDeadline<'a>
+            )
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncTimedRwLock,
+            {
+                let deadline = self.deadline;
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::read_until(&self.locks.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self.locks,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Locks `rw_lock` for writing, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]    pub fn write_rw_lock < 'a > (& 'a self) -> (impl :: std :: future :: Future < Output = :: std :: result :: Result < < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: WriteGuard < 'a > , :: locktree :: DeadlineExceeded > > + 'a , MainLockTreeRwLockDeadline < 'a >) where for < 'x > :: locktree :: async_std :: sync :: RwLock < () > : :: locktree :: AsyncTimedRwLock ,{
+                let deadline = self.deadline;
+                (
+                    ::locktree::deadline::lock_async(
+                        \"rw_lock\",
+                        ::locktree::AsyncTimedRwLock::write_until(&self.locks.rw_lock, deadline)
+                    ),
+                    MainLockTreeRwLockDeadline {
+                        locks: self.locks,
+                        deadline: deadline
+                    },
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting.\"]
+            pub fn try_read_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_read(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting.\"]
+            pub fn try_write_rw_lock<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                MainLockTreeRwLockDeadline<'a>
+            )> {
+                match ::locktree::AsyncRwLock::try_write(&self.locks.rw_lock) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRwLockDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`. It can't take any other lock.\"]
+        struct MainLockTreeRwLock<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeRwLock<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+            ) -> < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncDowngradableRwLock,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncDowngradableRwLock> ::downgrade(guard)
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_read_rw_lock<U, F>(
+                &self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>,
+                f: F,
+            ) -> < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'b, U>
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x U,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::map_read(guard, f)
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_read_rw_lock<U, F>(
+                &self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedReadGuard<'b, U>,
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            >
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x U>,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_read(guard, f)
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]
+            pub fn map_write_rw_lock<U, F>(
+                &self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+                f: F,
+            ) -> < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'b, U>
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> &'x mut U,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::map_write(guard, f)
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_write_rw_lock<U, F>(
+                &self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::MappedWriteGuard<'b, U>,
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>
+            >
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncMappableRwLock,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_write(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `rw_lock`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeRwLockDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRwLockDeadline<'b> {
+            #[doc = \"Tries to lock `rw_lock` for reading without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_read_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_read(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `rw_lock` for writing without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_write_rw_lock_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'a>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::AsyncRwLock::try_write(&self.locks.rw_lock),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Downgrades a write guard of `rw_lock` to a read guard.\"]
+            pub fn downgrade_rw_lock(
+                &self,
+                guard: < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::WriteGuard<'b>,
+            ) -> < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncRwLock> ::ReadGuard<'b>
+            where
+                for<'x> ::locktree::async_std::sync::RwLock<()> : ::locktree::AsyncDowngradableRwLock,
+            {
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncDowngradableRwLock> ::downgrade(
+                    guard
+                )
+            }
+
+            #[doc = \"Narrows a read guard of `rw_lock` to a part of the locked data.\"]    pub fn map_read_rw_lock < U , F > (& self , guard : < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: ReadGuard < 'b > , f : F ,) -> < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: MappedReadGuard < 'b , U > where for < 'x > :: locktree :: async_std :: sync :: RwLock < () > : :: locktree :: AsyncMappableRwLock , U : ? Sized + 'b , F : for < 'x > :: std :: ops :: FnOnce (& 'x < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: Target) -> & 'x U ,{
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::map_read(
+                    guard, f
+                )
+            }
+
+            #[doc = \"Like `map_read_rw_lock`, but gives the guard back if `f` returns `None`.\"]    pub fn try_map_read_rw_lock < U , F > (& self , guard : < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: ReadGuard < 'b > , f : F ,) -> :: std :: result :: Result < < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: MappedReadGuard < 'b , U > , < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: ReadGuard < 'b > > where for < 'x > :: locktree :: async_std :: sync :: RwLock < () > : :: locktree :: AsyncMappableRwLock , U : ? Sized + 'b , F : for < 'x > :: std :: ops :: FnOnce (& 'x < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: Target) -> :: std :: option :: Option < & 'x U > ,{
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_read(
+                    guard, f
+                )
+            }
+
+            #[doc = \"Narrows a write guard of `rw_lock` to a part of the locked data.\"]    pub fn map_write_rw_lock < U , F > (& self , guard : < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: WriteGuard < 'b > , f : F ,) -> < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: MappedWriteGuard < 'b , U > where for < 'x > :: locktree :: async_std :: sync :: RwLock < () > : :: locktree :: AsyncMappableRwLock , U : ? Sized + 'b , F : for < 'x > :: std :: ops :: FnOnce (& 'x mut < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: Target) -> & 'x mut U ,{
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::map_write(
+                    guard, f
+                )
+            }
+
+            #[doc = \"Like `map_write_rw_lock`, but gives the guard back if `f` returns `None`.\"]    pub fn try_map_write_rw_lock < U , F > (& self , guard : < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: WriteGuard < 'b > , f : F ,) -> :: std :: result :: Result < < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: MappedWriteGuard < 'b , U > , < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncRwLock > :: WriteGuard < 'b > > where for < 'x > :: locktree :: async_std :: sync :: RwLock < () > : :: locktree :: AsyncMappableRwLock , U : ? Sized + 'b , F : for < 'x > :: std :: ops :: FnOnce (& 'x mut < :: locktree :: async_std :: sync :: RwLock < () > as :: locktree :: AsyncMappableRwLock > :: Target) -> :: std :: option :: Option < & 'x mut U > ,{
+                < ::locktree::async_std::sync::RwLock<()> as ::locktree::AsyncMappableRwLock> ::try_map_write(
+                    guard, f
+                )
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_apply_poison_policies() {
+    compare_input_output(
+        "
+        #[poison(recover)]
+        Main {
+            mutex: Mutex<()>
+        }
+        ",
+        "
+        struct MainLockTree {
+            mutex: ::locktree::poison::RecoverMutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(mutex_value: ()) -> Self {
+                Self {
+                    mutex: ::locktree::New::new(mutex_value),
+                }
+            }
+
+            #[doc = \"Locks `mutex`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                MainLockTreeMutex<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.mutex), MainLockTreeMutex { locks: self })
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                MainLockTreeMutex<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up after `timeout`.\"]
+            pub fn lock_mutex_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.mutex, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`.\"]
+            pub fn lock_mutex_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                MainLockTreeMutex<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.mutex, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeMutex { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `mutex`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_mutex_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Clears the poison of `mutex`.\"]
+            pub fn clear_poison_mutex(&self) {
+                ::locktree::ClearPoison::clear_poison(&self.mutex)
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                f: F,
+            ) -> < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::locktree::poison::RecoverMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>
+            >
+            where
+                for<'x> ::locktree::poison::RecoverMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `mutex`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_mutex<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                    MainLockTreeMutexDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"mutex\",
+                    ::locktree::Mutex::lock_until(&self.locks.mutex, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `mutex` without waiting.\"]
+            pub fn try_lock_mutex<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                MainLockTreeMutexDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.mutex) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeMutexDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`. It can't take any other lock.\"]
+        struct MainLockTreeMutex<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeMutex<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::poison::RecoverMutex<()>>,
+                f: F,
+            ) -> < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::locktree::poison::RecoverMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::poison::RecoverMutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::locktree::poison::RecoverMutex<()>>
+            >
+            where
+                for<'x> ::locktree::poison::RecoverMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `mutex`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeMutexDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeMutexDeadline<'b> {
+            #[doc = \"Tries to lock `mutex` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_mutex_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::locktree::poison::RecoverMutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.mutex),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `mutex` to a part of the locked data.\"]
+            pub fn map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::poison::RecoverMutex<()>>,
+                f: F,
+            ) -> < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::locktree::poison::RecoverMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_mutex`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_mutex<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::locktree::poison::RecoverMutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::locktree::poison::RecoverMutex<()>>
+            >
+            where
+                for<'x> ::locktree::poison::RecoverMutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::locktree::poison::RecoverMutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_invalid_poison_policies() {
+    compare_input_errors(
+        "
+        #[poison(ignore)]
+        Main {
+            #[poison(recover)]
+            #[poison(propagate)]
+            mutex: Mutex<()>,
+            #[poison(recover)]
+            super_mutex: Mutex(SuperMutex)<()>,
+            #[poison]
+            rw_lock: RwLock<()>,
+        }
+        ",
+        &[
+            "expected one of: `panic`, `recover`, `propagate`",
+            "duplicate `poison` attribute",
+            "poison policies only apply to `Mutex` and `RwLock` locks without an explicit HKT",
+            "expected attribute arguments in parentheses: #[poison(...)]",
+        ],
+    );
+}
+
+#[test]
+fn should_handle_branches() {
+    compare_input_output(
+        "
+        Main {
+            root: Mutex<()> {
+                left: Mutex<()>,
+            } {
+                right: Mutex<()>,
+            },
+            last: Mutex<()>,
+        }
+        ",
+        "
+        struct MainLockTree {
+            root: ::std::sync::Mutex<()>,
+            left: ::std::sync::Mutex<()>,
+            right: ::std::sync::Mutex<()>,
+            last: ::std::sync::Mutex<()>,
+        }
+
+        impl MainLockTree {
+            #[doc = \"Creates the locktree from the initial value of each lock.\"]
+            fn new(root_value: (), left_value: (), right_value: (), last_value: ()) -> Self {
+                Self {
+                    root: ::locktree::New::new(root_value),
+                    left: ::locktree::New::new(left_value),
+                    right: ::locktree::New::new(right_value),
+                    last: ::locktree::New::new(last_value),
+                }
+            }
+
+            #[doc = \"Locks `root`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_root<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRoot<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.root), MainLockTreeRoot { locks: self })
+            }
+
+            #[doc = \"Tries to lock `root` without waiting.\"]
+            pub fn try_lock_root<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRoot<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.root) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRoot { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `root`, giving up after `timeout`.\"]
+            pub fn lock_root_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRoot<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.root, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRoot { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `root`, giving up at `deadline`.\"]
+            pub fn lock_root_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRoot<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.root, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRoot { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `root`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_root_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeRootDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"root\",
+                    ::locktree::Mutex::lock_until(&self.root, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRootDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `root` to a part of the locked data.\"]
+            pub fn map_root<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_root`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_root<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `left`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_left<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.left), MainLockTreeLeft { locks: self })
+            }
+
+            #[doc = \"Tries to lock `left` without waiting.\"]
+            pub fn try_lock_left<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.left) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLeft { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `left`, giving up after `timeout`.\"]
+            pub fn lock_left_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.left, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLeft { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `left`, giving up at `deadline`.\"]
+            pub fn lock_left_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.left, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLeft { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `left`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_left_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLeftDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"left\",
+                    ::locktree::Mutex::lock_until(&self.left, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `left` to a part of the locked data.\"]
+            pub fn map_left<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_left`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_left<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `right`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_right<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.right), MainLockTreeRight { locks: self })
+            }
+
+            #[doc = \"Tries to lock `right` without waiting.\"]
+            pub fn try_lock_right<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.right) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRight { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up after `timeout`.\"]
+            pub fn lock_right_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.right, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRight { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up at `deadline`.\"]
+            pub fn lock_right_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.right, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRight { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_right_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeRightDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"right\",
+                    ::locktree::Mutex::lock_until(&self.right, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `right` to a part of the locked data.\"]
+            pub fn map_right<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_right`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_right<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+
+            #[doc = \"Locks `last`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_last<'a>(
+                &'a self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.last), MainLockTreeLast { locks: self })
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up after `timeout`.\"]
+            pub fn lock_last_for<'a>(
+                &'a self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at `deadline`.\"]
+            pub fn lock_last_until<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at `deadline`. The returned forward locktree keeps that deadline.\"]
+            pub fn lock_last_with_deadline<'a>(
+                &'a self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Narrows a guard of `last` to a part of the locked data.\"]
+            pub fn map_last<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_last`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_last<'a, U, F>(
+                &'a self,
+                guard: ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'a, U>,
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'a,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Entry point of `MainLockTree` whose accessors give up at a deadline, returned by `WithDeadline::with_deadline`.\"]
+        struct MainLockTreeDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeDeadline<'b> {
+            #[doc = \"Locks `root`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_root<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeRootDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"root\",
+                    ::locktree::Mutex::lock_until(&self.locks.root, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRootDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting.\"]
+            pub fn try_lock_root<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRootDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.root) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRootDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `left`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_left<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLeftDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"left\",
+                    ::locktree::Mutex::lock_until(&self.locks.left, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `left` without waiting.\"]
+            pub fn try_lock_left<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeftDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.left) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_right<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeRightDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"right\",
+                    ::locktree::Mutex::lock_until(&self.locks.right, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `right` without waiting.\"]
+            pub fn try_lock_right<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRightDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.right) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_last<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl ::locktree::WithDeadline for MainLockTree {
+            type Deadline<'b>
+                = MainLockTreeDeadline<'b>
+            where
+                Self: 'b;
+
+            fn with_deadline(&self, deadline: ::std::time::Instant) -> Self::Deadline<'_> {
+                MainLockTreeDeadline {
+                    locks: self,
+                    deadline
+                }
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `root`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `left`\"]
+        #[doc = \"- `right`\"]
+        #[doc = \"- `last`\"]
+        struct MainLockTreeRoot<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeRoot<'b> {
+            #[doc = \"Locks `left`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_left<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.left), MainLockTreeLeft { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `left` without waiting.\"]
+            pub fn try_lock_left<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.left) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLeft { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `left`, giving up after `timeout`.\"]
+            pub fn lock_left_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.left, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLeft { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `left`, giving up at `deadline`.\"]
+            pub fn lock_left_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.left, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLeft { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `right`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_right<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.right), MainLockTreeRight { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `right` without waiting.\"]
+            pub fn try_lock_right<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.right) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRight { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up after `timeout`.\"]
+            pub fn lock_right_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.right, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRight { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up at `deadline`.\"]
+            pub fn lock_right_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.right, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRight { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `last`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_last<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.last), MainLockTreeLast { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up after `timeout`.\"]
+            pub fn lock_last_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at `deadline`.\"]
+            pub fn lock_last_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_left`, but consumes this forward locktree.\"]
+            pub fn into_lock_left(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.left), MainLockTreeLeft { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_left`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_left(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.left) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLeft { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_left_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_left_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.left, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLeft { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_left_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_left_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLeft<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.left, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLeft { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_right`, but consumes this forward locktree.\"]
+            pub fn into_lock_right(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.right), MainLockTreeRight { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_right`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_right(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.right) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeRight { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_right_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_right_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.right, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRight { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_right_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_right_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeRight<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.right, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeRight { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_last`, but consumes this forward locktree.\"]
+            pub fn into_lock_last(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.last), MainLockTreeLast { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_last`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_last(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_last_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_last_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_last_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_last_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `root` to a part of the locked data.\"]
+            pub fn map_root<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_root`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_root<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `root`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `left`\"]
+        #[doc = \"- `right`\"]
+        #[doc = \"- `last`\"]
+        struct MainLockTreeRootDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRootDeadline<'b> {
+            #[doc = \"Locks `left`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_left<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLeftDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"left\",
+                    ::locktree::Mutex::lock_until(&self.locks.left, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `left` without waiting.\"]
+            pub fn try_lock_left<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLeftDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.left) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `right`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_right<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeRightDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"right\",
+                    ::locktree::Mutex::lock_until(&self.locks.right, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `right` without waiting.\"]
+            pub fn try_lock_right<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeRightDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.right) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_last<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_left`, but consumes this forward locktree.\"]
+            pub fn into_lock_left(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeLeftDeadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"left\",
+                    ::locktree::Mutex::lock_until(&self.locks.left, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_left`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_left(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLeftDeadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.left) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLeftDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_right`, but consumes this forward locktree.\"]
+            pub fn into_lock_right(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeRightDeadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"right\",
+                    ::locktree::Mutex::lock_until(&self.locks.right, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_right`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_right(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeRightDeadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.right) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeRightDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_last`, but consumes this forward locktree.\"]
+            pub fn into_lock_last(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_last`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_last(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `root` to a part of the locked data.\"]
+            pub fn map_root<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_root`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_root<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `left`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `last`\"]
+        struct MainLockTreeLeft<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeLeft<'b> {
+            #[doc = \"Locks `last`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_last<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.last), MainLockTreeLast { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up after `timeout`.\"]
+            pub fn lock_last_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at `deadline`.\"]
+            pub fn lock_last_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_last`, but consumes this forward locktree.\"]
+            pub fn into_lock_last(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.last), MainLockTreeLast { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_last`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_last(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_last_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_last_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_last_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_last_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `left` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_left_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.left),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `right` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_right_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.right),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `left` to a part of the locked data.\"]
+            pub fn map_left<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_left`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_left<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `left`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `last`\"]
+        struct MainLockTreeLeftDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeLeftDeadline<'b> {
+            #[doc = \"Locks `last`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_last<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_last`, but consumes this forward locktree.\"]
+            pub fn into_lock_last(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_last`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_last(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `left` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_left_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.left),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `right` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_right_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.right),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `left` to a part of the locked data.\"]
+            pub fn map_left<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_left`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_left<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `right`.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `last`\"]
+        struct MainLockTreeRight<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeRight<'b> {
+            #[doc = \"Locks `last`. Returns its guard along with a forward locktree to take the next locks.\"]
+            pub fn lock_last<'a>(
+                &'a mut self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.last), MainLockTreeLast { locks: self.locks })
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up after `timeout`.\"]
+            pub fn lock_last_for<'a>(
+                &'a mut self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Locks `last`, giving up at `deadline`.\"]
+            pub fn lock_last_until<'a>(
+                &'a mut self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'a>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_last`, but consumes this forward locktree.\"]
+            pub fn into_lock_last(
+                self
+            ) -> (
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ) {
+                (::locktree::Mutex::lock(&self.locks.last), MainLockTreeLast { locks: self.locks })
+            }
+
+            #[doc = \"Like `try_lock_last`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_last(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_last_for`, but consumes this forward locktree.\"]
+            pub fn into_lock_last_for(
+                self,
+                timeout: ::std::time::Duration
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_for(&self.locks.last, timeout) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `lock_last_until`, but consumes this forward locktree.\"]
+            pub fn into_lock_last_until(
+                self,
+                deadline: ::std::time::Instant
+            ) -> ::std::result::Result<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLast<'b>
+            ), ::locktree::TimedOut> {
+                match ::locktree::Mutex::lock_until(&self.locks.last, deadline) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((guard, MainLockTreeLast { locks: self.locks }))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `left` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_left_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.left),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `right` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_right_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.right),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `right` to a part of the locked data.\"]
+            pub fn map_right<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_right`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_right<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `right`, whose accessors give up at a deadline.\"]
+        #[doc = \"\"]
+        #[doc = \"It can still take:\"]
+        #[doc = \"- `last`\"]
+        struct MainLockTreeRightDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeRightDeadline<'b> {
+            #[doc = \"Locks `last`, giving up at the deadline. Returns its guard along with a forward locktree keeping that deadline.\"]
+            pub fn lock_last<'a>(
+                &'a mut self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'a>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Tries to lock `last` without waiting.\"]
+            pub fn try_lock_last<'a>(
+                &'a mut self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'a>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Like `lock_last`, but consumes this forward locktree.\"]
+            pub fn into_lock_last(
+                self
+            ) -> ::std::result::Result<
+                (
+                    ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                    MainLockTreeLastDeadline<'b>
+                ),
+                ::locktree::DeadlineExceeded
+            > {
+                let deadline = self.deadline;
+                match ::locktree::deadline::lock(
+                    \"last\",
+                    ::locktree::Mutex::lock_until(&self.locks.last, deadline)
+                ) {
+                    ::std::result::Result::Ok(guard) => {
+                        ::std::result::Result::Ok((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: deadline
+                            }
+                        ))
+                    }
+                    ::std::result::Result::Err(error) => ::std::result::Result::Err(error),
+                }
+            }
+
+            #[doc = \"Like `try_lock_last`, but consumes this forward locktree.\"]
+            pub fn into_try_lock_last(
+                self
+            ) -> ::std::option::Option<(
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                MainLockTreeLastDeadline<'b>
+            )> {
+                match ::locktree::Mutex::try_lock(&self.locks.last) {
+                    ::std::option::Option::Some(guard) => {
+                        ::std::option::Option::Some((
+                            guard,
+                            MainLockTreeLastDeadline {
+                                locks: self.locks,
+                                deadline: self.deadline
+                            }
+                        ))
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `left` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_left_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.left),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `right` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_right_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.right),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `right` to a part of the locked data.\"]
+            pub fn map_right<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_right`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_right<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `last`. It can't take any other lock.\"]
+        struct MainLockTreeLast<'b> {
+            locks: &'b MainLockTree
+        }
+
+        impl<'b> MainLockTreeLast<'b> {
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `left` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_left_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.left),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `right` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_right_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.right),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `last` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_last_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.last),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `last` to a part of the locked data.\"]
+            pub fn map_last<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_last`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_last<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(&'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+
+        #[doc = \"Forward locktree after locking `last`, whose accessors give up at a deadline. It can't take any other lock.\"]
+        struct MainLockTreeLastDeadline<'b> {
+            locks: &'b MainLockTree,
+            deadline: ::std::time::Instant
+        }
+
+        impl<'b> MainLockTreeLastDeadline<'b> {
+            #[doc = \"Tries to lock `root` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_root_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.root),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `left` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_left_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.left),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `right` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_right_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.right),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Tries to lock `last` without waiting, although it doesn't come after the locks taken through this forward locktree.\"]
+            pub fn try_lock_last_out_of_order<'a>(
+                &'a self
+            ) -> ::std::result::Result<
+                ::locktree::PluggedMutexGuard<'a, ::std::sync::Mutex<()>>,
+                ::locktree::WouldBlock
+            > {
+                ::std::option::Option::ok_or(
+                    ::locktree::Mutex::try_lock(&self.locks.last),
+                    ::locktree::WouldBlock,
+                )
+            }
+
+            #[doc = \"Narrows a guard of `last` to a part of the locked data.\"]
+            pub fn map_last<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> &'x mut U,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::map(guard, f)
+            }
+
+            #[doc = \"Like `map_last`, but gives the guard back if `f` returns `None`.\"]
+            pub fn try_map_last<U, F>(
+                &self,
+                guard: ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>,
+                f: F,
+            ) -> ::std::result::Result<
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::MappedGuard<'b, U>,
+                ::locktree::PluggedMutexGuard<'b, ::std::sync::Mutex<()>>
+            >
+            where
+                for<'x> ::std::sync::Mutex<()> : ::locktree::MappableMutex,
+                U: ?Sized + 'b,
+                F: for<'x> ::std::ops::FnOnce(
+                    &'x mut < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::Target
+                ) -> ::std::option::Option< &'x mut U>,
+            {
+                < ::std::sync::Mutex<()> as ::locktree::MappableMutex> ::try_map(guard, f)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_duplicate_trees() {
+    compare_input_errors(
+        "
+        Main {}
+        Main {}
+        ",
+        &["duplicate lock tree `Main`"],
+    );
+}
+
+#[test]
+fn should_report_duplicate_locks() {
+    compare_input_errors(
+        "
+        Main {
+            mutex: Mutex<()>,
+            mutex: RwLock<()>,
+        }
+        ",
+        &["duplicate lock `mutex`"],
+    );
+}
+
+#[test]
+fn should_report_duplicate_locks_across_branches() {
+    compare_input_errors(
+        "
+        Main {
+            root: Mutex<()> {
+                mutex: Mutex<()>,
+            } {
+                mutex: Mutex<()>,
+            },
+        }
+        ",
+        &["duplicate lock `mutex`"],
+    );
+}
+
+#[test]
+fn should_report_reserved_lock_names() {
+    compare_input_errors(
+        "
+        Main {
+            new: Mutex<()>,
+            locks: Mutex<()>,
+            deadline: Mutex<()>,
+        }
+
+        #[builder]
+        Built {
+            build: Mutex<()>,
+            builder: Mutex<()>,
+        }
+        ",
+        &[
+            "`new` is reserved for items generated by `locktree!`",
+            "`locks` is reserved for items generated by `locktree!`",
+            "`deadline` is reserved for items generated by `locktree!`",
+            "`build` is reserved for items generated by `#[builder]`",
+            "`builder` is reserved for items generated by `#[builder]`",
+        ],
+    );
+}
+
+#[test]
+fn should_report_raw_identifiers_by_their_unraw_name() {
+    compare_input_errors(
+        "
+        Main {
+            r#new: Mutex<()>,
+            mutex: Mutex<()>,
+            r#mutex: Mutex<()>,
+        }
+        ",
+        &[
+            "`new` is reserved for items generated by `locktree!`",
+            "duplicate lock `mutex`",
+        ],
+    );
+}
+
+#[test]
+fn should_report_clashing_forward_names() {
+    compare_input_errors(
+        "
+        Main {
+            mutex: Mutex<()>,
+            _mutex: Mutex<()>,
+            __: Mutex<()>,
+            queue: Mutex<()>,
+            queue_deadline: Mutex<()>,
+        }
+        ",
+        &[
+            "`mutex` and `_mutex` produce the same forward struct name",
+            "`__` does not produce a forward struct name",
+            "`queue` and `queue_deadline` produce the same forward struct name",
+        ],
+    );
+}
+
+#[test]
+fn should_report_clashing_function_names() {
+    compare_input_errors(
+        "
+        Main {
+            first: Mutex<()>,
+            lock_first: Shared<()>,
+            second: Mutex<()>,
+            second_for: Mutex<()>,
+            storage: Tree<StorageLockTree>,
+            enter_storage: Shared<()>,
+        }
+        ",
+        &[
+            "`first` and `lock_first` both generate `lock_first`",
+            "`second` and `second_for` both generate `lock_second_for`",
+            "`storage` and `enter_storage` both generate `enter_storage`",
+        ],
+    );
+}
+
+#[test]
+fn should_report_every_error_in_one_pass() {
+    compare_input_errors(
+        "
+        Main {
+            mutex0: async Mutex<()>,
+            mutex1: Mutex(SuperMutex Extra)<()>,
+            mutex2: Mutex<()>,
+            mutex2: Mutex<()>,
+        }
+        Other {
+            mutex: Semaphore<()>,
+        }
+        Main {}
+        ",
+        &[
+            "async locks must have an explicit HKT",
+            "unexpected token",
+            "duplicate lock `mutex2`",
+            "expected one of: `Mutex`, `RwLock`, `UpgradableRwLock`, `Tree`, `Shared`",
+            "duplicate lock tree `Main`",
+        ],
+    );
+}
+
+#[test]
+fn should_accept_duplicate_locks_under_different_cfg_attributes() {
+    compare_input_errors(
+        r#"
+        Main {
+            #[cfg(feature = "small")]
+            mutex: Mutex<u8>,
+            #[cfg(not(feature = "small"))]
+            mutex: Mutex<u16>,
+            #[cfg(feature = "small")]
+            rw_lock: RwLock<u8>,
+            #[cfg(feature = "small")]
+            rw_lock: RwLock<u16>,
+        }
+        "#,
+        &["duplicate lock `rw_lock`"],
+    );
+}
+
+#[test]
+fn should_skip_generic_arguments_of_invalid_locks() {
+    compare_input_errors(
+        "
+        Main {
+            map: Mutx<HashMap<u8, u16>>,
+            callback: Mutx<Box<dyn Fn(u8, u16) -> Vec<u8>>>,
+            mutex: Mutex<()>,
+            mutex: Mutex<()>,
+        }
+        ",
+        &[
+            "expected one of: `Mutex`, `RwLock`, `UpgradableRwLock`, `Tree`, `Shared`",
+            "expected one of: `Mutex`, `RwLock`, `UpgradableRwLock`, `Tree`, `Shared`",
+            "duplicate lock `mutex`",
+        ],
+    );
+}
+
+#[test]
+fn should_output_compile_errors() {
+    assert_eq!(
+        locktree_impl(
+            syn::parse_str("Main { mutex: Mutex<()> } Main {}").unwrap()
+        )
+        .to_string(),
+        quote::quote! {
+            compile_error! { "duplicate lock tree `Main`" }
+        }
+        .to_string(),
+    );
+}
+
+#[test]
+fn should_chain_locks_in_lock_many() {
+    assert_eq!(
+        lock_many_impl(
+            syn::parse_str("locks.lock_mutex0(), read_rw_lock, lock_mutex1")
+                .unwrap()
+        )
+        .to_string(),
+        syn::parse_str::<TokenStream>(
+            "
+            {
+                let (guard0, forward) = locks.lock_mutex0();
+                let (guard1, forward) = forward.into_read_rw_lock();
+                let (guard2, forward) = forward.into_lock_mutex1();
+                ((guard0, guard1, guard2,), forward)
+            }
+            "
+        )
+        .unwrap()
+        .to_string()
+    );
+}
+
+#[test]
+fn should_implement_a_mutex_backend() {
+    compare_backend_output(
+        "
+        impl<T> Mutex for SuperMutex<T> where T: Send {
+            type Guard<'a> = SuperGuard<'a, T>;
+
+            fn lock = acquire;
+        }
+        ",
+        "
+        impl<T> ::locktree::New<T> for SuperMutex<T> where T: Send {
+            fn new(value: T) -> Self {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn new<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperMutex<T>> ::new(value)
+                }
+            }
+        }
+
+        impl<T> ::locktree::Mutex for SuperMutex<T> where T: Send {
+            type Guard<'a> = SuperGuard<'a, T>
+            where
+                Self: 'a;
+
+            fn lock(&self) -> Self::Guard<'_> {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn acquire<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperMutex<T>> ::acquire(self)
+                }
+            }
+
+            fn try_lock(
+                &self
+            ) -> ::std::option::Option<Self::Guard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_lock<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperMutex<T>> ::try_lock(self)
+                    }
+                )
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_return_async_backend_futures_as_is() {
+    compare_backend_output(
+        "
+        impl<T> AsyncRwLock for SuperRwLock<T> {
+            type ReadGuard<'a> = SuperReadGuard<'a, T>;
+            type WriteGuard<'a> = SuperWriteGuard<'a, T>;
+        }
+        ",
+        "
+        impl<T> ::locktree::New<T> for SuperRwLock<T> {
+            fn new(value: T) -> Self {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn new<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::new(value)
+                }
+            }
+        }
+
+        impl<T> ::locktree::AsyncRwLock for SuperRwLock<T> {
+            type ReadGuard<'a> = SuperReadGuard<'a, T>
+            where
+                Self: 'a;
+
+            type WriteGuard<'a> = SuperWriteGuard<'a, T>
+            where
+                Self: 'a;
+
+            fn read(
+                &self
+            ) -> impl ::std::future::Future<
+                Output = Self::ReadGuard<'_>
+            > + '_ {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn read<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::read(self)
+                }
+            }
+
+            fn try_read(
+                &self
+            ) -> ::std::option::Option<Self::ReadGuard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_read<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::try_read(self)
+                    }
+                )
+            }
+
+            fn write(
+                &self
+            ) -> impl ::std::future::Future<
+                Output = Self::WriteGuard<'_>
+            > + '_ {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn write<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::write(self)
+                }
+            }
+
+            fn try_write(
+                &self
+            ) -> ::std::option::Option<Self::WriteGuard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_write<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::try_write(self)
+                    }
+                )
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_implement_async_timed_backends_when_given_a_deadline_method() {
+    compare_backend_output(
+        "
+        impl<T> AsyncRwLock for SuperRwLock<T> {
+            type ReadGuard<'a> = SuperReadGuard<'a, T>;
+            type WriteGuard<'a> = SuperWriteGuard<'a, T>;
+
+            fn write_until = write_before;
+        }
+        ",
+        "
+        impl<T> ::locktree::New<T> for SuperRwLock<T> {
+            fn new(value: T) -> Self {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn new<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::new(value)
+                }
+            }
+        }
+
+        impl<T> ::locktree::AsyncRwLock for SuperRwLock<T> {
+            type ReadGuard<'a> = SuperReadGuard<'a, T>
+            where
+                Self: 'a;
+
+            type WriteGuard<'a> = SuperWriteGuard<'a, T>
+            where
+                Self: 'a;
+
+            fn read(
+                &self
+            ) -> impl ::std::future::Future<
+                Output = Self::ReadGuard<'_>
+            > + '_ {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn read<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::read(self)
+                }
+            }
+
+            fn try_read(
+                &self
+            ) -> ::std::option::Option<Self::ReadGuard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_read<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::try_read(self)
+                    }
+                )
+            }
+
+            fn write(
+                &self
+            ) -> impl ::std::future::Future<
+                Output = Self::WriteGuard<'_>
+            > + '_ {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn write<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::write(self)
+                }
+            }
+
+            fn try_write(
+                &self
+            ) -> ::std::option::Option<Self::WriteGuard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_write<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::try_write(self)
+                    }
+                )
+            }
+        }
+
+        impl<T> ::locktree::AsyncTimedRwLock for SuperRwLock<T> {
+            fn read_until(
+                &self,
+                deadline: ::std::time::Instant
+            ) -> impl ::std::future::Future<
+                Output = ::std::result::Result<
+                    Self::ReadGuard<'_>,
+                    ::locktree::TimedOut
+                >
+            > + '_ {
+                ::locktree::backend::timed_out(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn read_until<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::read_until(self, deadline)
+                    }
+                )
+            }
+
+            fn write_until(
+                &self,
+                deadline: ::std::time::Instant
+            ) -> impl ::std::future::Future<
+                Output = ::std::result::Result<
+                    Self::WriteGuard<'_>,
+                    ::locktree::TimedOut
+                >
+            > + '_ {
+                ::locktree::backend::timed_out(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn write_before<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::write_before(self, deadline)
+                    }
+                )
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_implement_downgrades_only_when_given() {
+    compare_backend_output(
+        "
+        impl<T> RwLock for SuperRwLock<T> {
+            type ReadGuard<'a> = SuperReadGuard<'a, T>;
+            type WriteGuard<'a> = SuperWriteGuard<'a, T>;
+
+            fn downgrade = SuperWriteGuard::into_read;
+        }
+        ",
+        "
+        impl<T> ::locktree::New<T> for SuperRwLock<T> {
+            fn new(value: T) -> Self {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn new<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::new(value)
+                }
+            }
+        }
+
+        impl<T> ::locktree::RwLock for SuperRwLock<T> {
+            type ReadGuard<'a> = SuperReadGuard<'a, T>
+            where
+                Self: 'a;
+
+            type WriteGuard<'a> = SuperWriteGuard<'a, T>
+            where
+                Self: 'a;
+
+            fn read(&self) -> Self::ReadGuard<'_> {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn read<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::read(self)
+                }
+            }
+
+            fn try_read(
+                &self
+            ) -> ::std::option::Option<Self::ReadGuard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_read<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::try_read(self)
+                    }
+                )
+            }
+
+            fn write(&self) -> Self::WriteGuard<'_> {
+                {
+                    #[allow(dead_code)]
+                    trait InherentMethod {
+                        fn write<A>(_: A) {}
+                    }
+                    impl<T: ?Sized> InherentMethod for T {}
+                    <SuperRwLock<T>> ::write(self)
+                }
+            }
+
+            fn try_write(
+                &self
+            ) -> ::std::option::Option<Self::WriteGuard<'_>> {
+                ::locktree::backend::IntoOption::into_option(
+                    {
+                        #[allow(dead_code)]
+                        trait InherentMethod {
+                            fn try_write<A>(_: A) {}
+                        }
+                        impl<T: ?Sized> InherentMethod for T {}
+                        <SuperRwLock<T>> ::try_write(self)
+                    }
+                )
+            }
+        }
+
+        impl<T> ::locktree::DowngradableRwLock for SuperRwLock<T> {
+            fn downgrade<'a>(
+                guard: Self::WriteGuard<'a>
+            ) -> Self::ReadGuard<'a>
+            where
+                Self: 'a,
+            {
+                SuperWriteGuard::into_read(guard)
+            }
+        }
+        ",
+    );
+}
+
+#[test]
+fn should_report_invalid_backends() {
+    let errors = |input| match syn::parse_str::<Backend>(input) {
+        Ok(_) => Vec::new(),
+        Err(error) => error.into_iter().map(|x| x.to_string()).collect(),
+    };
+
+    assert_eq!(
+        errors("impl<T> Lock for SuperMutex<T> {}"),
+        ["expected one of: `Mutex`, `RwLock`, `AsyncMutex`, `AsyncRwLock`"],
+    );
+    assert_eq!(
+        errors("impl Mutex for SuperMutex {}"),
+        [
+            "expected a lock type taking the locked data as its last generic \
+             argument"
+        ],
+    );
+    assert_eq!(
+        errors(
+            "
+            impl<T> RwLock for SuperRwLock<T> {
+                type ReadGuard<'a> = SuperGuard<'a, T>;
+                type ReadGuard<'a> = SuperGuard<'a, T>;
+                type Guard<'a> = SuperGuard<'a, T>;
+                fn lock = acquire;
+                fn downgrade = SuperGuard::downgrade;
+                fn downgrade = SuperGuard::downgrade;
+            }
+            "
+        ),
+        [
+            "duplicate `type ReadGuard`",
+            "unexpected type, expected one of: `ReadGuard`, `WriteGuard`",
+            "unexpected method, expected one of: `new`, `read`, `try_read`, \
+             `write`, `try_write`, `downgrade`",
+            "duplicate `fn downgrade`",
+            "missing `type WriteGuard`",
+        ],
+    );
+}
+
+fn compare_input_output(input: &str, output: &str) {
+    assert_eq!(
+        normalize(locktree_impl(syn::parse_str(input).unwrap())),
+        normalize(syn::parse_str(output).unwrap()),
+    );
+}
+
+fn compare_backend_output(input: &str, output: &str) {
+    assert_eq!(
+        normalize(backend_impl(syn::parse_str(input).unwrap())),
+        normalize(syn::parse_str(output).unwrap()),
+    );
+}
+
+/// Render `tokens`, ignoring differences in spacing around lifetimes and
+/// punctuation.
+fn normalize(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" '", "'")
+        .replace(" ,", ",")
+        .replace(" >", ">")
+}
+
+fn compare_input_errors(input: &str, errors: &[&str]) {
+    let actual = match syn::parse_str::<LockTree>(input) {
+        Ok(_) => Vec::new(),
+        Err(error) => error.into_iter().map(|x| x.to_string()).collect(),
+    };
+
+    assert_eq!(actual, errors);
 }
@@ -0,0 +1,35 @@
+//! Support code for the lock trait implementations generated by `backend!`.
+
+#[cfg(feature = "async")]
+use crate::TimedOut;
+#[cfg(feature = "async")]
+use std::future::Future;
+
+/// Result of a non-blocking lock method, which holds a guard on success.
+pub trait IntoOption<G> {
+    fn into_option(self) -> Option<G>;
+}
+
+impl<G> IntoOption<G> for Option<G> {
+    fn into_option(self) -> Option<G> {
+        self
+    }
+}
+
+impl<G, E> IntoOption<G> for Result<G, E> {
+    fn into_option(self) -> Option<G> {
+        self.ok()
+    }
+}
+
+/// Await the future of a timed lock method, whose result holds a guard unless
+/// the deadline was reached first.
+#[cfg(feature = "async")]
+pub async fn timed_out<G, R>(
+    lock: impl Future<Output = R>,
+) -> Result<G, TimedOut>
+where
+    R: IntoOption<G>,
+{
+    lock.await.into_option().ok_or(TimedOut)
+}
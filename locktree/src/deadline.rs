@@ -0,0 +1,23 @@
+//! Support code for the accessors waiting until a deadline generated by
+//! `locktree!`.
+
+use crate::{DeadlineExceeded, TimedOut};
+#[cfg(feature = "async")]
+use std::future::Future;
+
+/// Report that `name` couldn't be acquired before the deadline.
+pub fn lock<G>(
+    name: &'static str,
+    guard: Result<G, TimedOut>,
+) -> Result<G, DeadlineExceeded> {
+    guard.map_err(|_| DeadlineExceeded { lock: name })
+}
+
+/// Like `lock`, but for `async` locks.
+#[cfg(feature = "async")]
+pub async fn lock_async<G>(
+    name: &'static str,
+    guard: impl Future<Output = Result<G, TimedOut>>,
+) -> Result<G, DeadlineExceeded> {
+    lock(name, guard.await)
+}
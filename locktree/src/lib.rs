@@ -4,12 +4,13 @@
 //!
 //! This crate revolves around a macro: `locktree`.
 //!
-//! # How `locktree` Works (TODO: update with latest format)
+//! # How `locktree` Works
 //!
 //! `locktree` (ab)uses Rust's type system to guarantee that locks are always
 //! taken in the same order. Locks under `locktree`'s management are organized
-//! into a linear sequence. Locks can only be acquired by moving forward into
-//! this sequence, and locks must be released when moving back. Thus it is
+//! into a sequence (or a tree of sequences, see [Branches](#branches)). Locks
+//! can only be acquired by moving forward into this sequence, and locks must
+//! be released when moving back. Thus it is
 //! statically impossible for two threads to acquire the same set of locks in
 //! different orders, and so deadlocks are impossible *as long as all locks are
 //! managed by `locktree`*.
@@ -22,14 +23,13 @@
 //! # use locktree::locktree;
 //! locktree! {
 //!   Main {
-//!     main: StdMutex<String>,
+//!     main: Mutex<String>,
 //!   }
 //! }
 //! ```
 //!
 //! The macro will generate an "entry point" with which you can lock anything
-//! (only `main` in this case, with explicit lifetimes for illustration
-//! purposes):
+//! (only `main` in this case):
 //!
 //! ```
 //! struct MainLockTree {
@@ -38,24 +38,27 @@
 //!
 //! impl MainLockTree {
 //!   fn lock_main<'a>(
-//!     &'a mut self
+//!     &'a self
 //!   ) -> (::std::sync::MutexGuard<'a, String>, MainLockTreeMain<'a>) {
 //!     // ...
 //!     # unimplemented!()
 //!   }
 //! }
-//! # struct MainLockTreeMain<'a>(std::marker::PhantomData<&'a ()>);
+//! # struct MainLockTreeMain<'a>(&'a MainLockTree);
 //! ```
 //!
-//! All lock functions take `&mut self` and return the appropriate lock and a
-//! *forward locktree*. Both are tied through their lifetimes to the
-//! `MainLockTree` instance, and thus it is impossible (in safe Rust) to use
-//! that instance to lock anything else. Further locking can only happen
-//! through the forward locktree. In this case, the `MainLockTreeMain` is
-//! completely empty and thus no further locks can be acquired:
+//! All lock functions return the appropriate guard and a *forward locktree*.
+//! Both are tied through their lifetimes to the `MainLockTree` instance.
+//! Further locking in sequence can only happen through the forward locktree.
+//! In this case, the `MainLockTreeMain` is completely empty and thus no further
+//! locks can be acquired:
 //!
 //! ```
-//! # struct MainLockTreeMain<'a>(::std::marker::PhantomData<&'a ()>);
+//! # struct MainLockTree;
+//! struct MainLockTreeMain<'b> {
+//!   locks: &'b MainLockTree,
+//! }
+//!
 //! impl<'b> MainLockTreeMain<'b> {}
 //! ```
 //!
@@ -66,8 +69,8 @@
 //! # use locktree::locktree;
 //! locktree! {
 //!   Main {
-//!     first: StdMutex<String>,
-//!     second: StdRwLock<Vec<usize>>,
+//!     first: Mutex<String>,
+//!     second: RwLock<Vec<usize>>,
 //!   }
 //! }
 //! ```
@@ -82,36 +85,42 @@
 //!
 //! impl MainLockTree {
 //!   fn lock_first<'a>(
-//!     &'a mut self
+//!     &'a self
 //!   ) -> (::std::sync::MutexGuard<'a, String>, MainLockTreeFirst<'a>) {
 //!     // ...
 //!     # unimplemented!()
 //!   }
 //!
 //!   fn read_second<'a>(
-//!     &'a mut self
+//!     &'a self
 //!   ) -> (::std::sync::RwLockReadGuard<'a, Vec<usize>>, MainLockTreeSecond<'a>) {
 //!     // ...
 //!     # unimplemented!()
 //!   }
 //!
 //!   fn write_second<'a>(
-//!     &'a mut self
+//!     &'a self
 //!   ) -> (::std::sync::RwLockWriteGuard<'a, Vec<usize>>, MainLockTreeSecond<'a>) {
 //!     // ...
 //!     # unimplemented!()
 //!   }
 //! }
+//! # struct MainLockTreeFirst<'a>(&'a MainLockTree);
+//! # struct MainLockTreeSecond<'a>(&'a MainLockTree);
 //! ```
 //!
 //! `MainLockTreeSecond` is again empty since it is the last in the sequence.
-//! However, `MainLockTreeFirst` allows `second` (but not `fist`) to be locked
+//! However, `MainLockTreeFirst` allows `second` (but not `first`) to be locked
 //! in sequence:
 //!
 //! ```
-//! struct MainLockTreeSecond<'a>(&'a MainLockTree);
+//! # struct MainLockTree;
+//! # struct MainLockTreeSecond<'a>(&'a MainLockTree);
+//! struct MainLockTreeFirst<'b> {
+//!   locks: &'b MainLockTree,
+//! }
 //!
-//! impl MainLockTree {
+//! impl<'b> MainLockTreeFirst<'b> {
 //!   fn read_second<'a>(
 //!     &'a mut self
 //!   ) -> (::std::sync::RwLockReadGuard<'a, Vec<usize>>, MainLockTreeSecond<'a>) {
@@ -128,180 +137,1928 @@
 //! }
 //! ```
 //!
-//! And thus a proper locking sequence is enforced. Note that you can choose
-//! not to lock anything between the current state and a target lock, but that
-//! will have to be dropped and reacquired if your code needs to lock anything
-//! that was skipped.
+//! Forward accessors take `&mut self`, so a forward can only have a single
+//! lock in sequence taken through it at a time. And thus a proper locking
+//! sequence is enforced. Note that you can choose not to lock anything between
+//! the current state and a target lock, but that will have to be dropped and
+//! reacquired if your code needs to lock anything that was skipped.
+//!
+//! # Branches
+//!
+//! A lock can be followed by one or more braced branches, each of them a
+//! sequence of locks of its own:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   Main {
+//!     config: RwLock<String>,
+//!     net: Mutex<()> {
+//!       sockets: Mutex<Vec<u16>>,
+//!       peers: Mutex<Vec<String>>,
+//!     } {
+//!       routes: Mutex<Vec<String>>,
+//!     },
+//!     stats: Mutex<u64>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(
+//!   String::new(),
+//!   (),
+//!   Vec::new(),
+//!   Vec::new(),
+//!   Vec::new(),
+//!   0,
+//! );
+//! let (_net, mut forward) = locks.lock_net();
+//! let (_routes, mut forward) = forward.lock_routes();
+//! let _stats = forward.lock_stats();
+//! ```
+//!
+//! Locks in a branch come after the lock owning the branch and before
+//! whatever follows that lock. Sibling branches have no order between them,
+//! so once a lock in one branch is taken only that branch can be descended
+//! into: `MainLockTreeRoutes` can lock `stats`, but not `sockets` or `peers`.
+//!
+//! # Visibility
+//!
+//! Generated structs and the `new` constructor are private by default. A
+//! visibility can be given before the tree name to export them:
+//!
+//! ```
+//! mod locks {
+//!   # use locktree::locktree;
+//!   locktree! {
+//!     pub Main {
+//!       main: Mutex<String>,
+//!     }
+//!   }
+//! }
+//!
+//! let locks = locks::MainLockTree::new(String::new());
+//! let (_main, _forward): (_, locks::MainLockTreeMain) = locks.lock_main();
+//! ```
+//!
+//! The fields of the generated structs always stay private, so code outside of
+//! the declaring module can only go through the accessors.
+//!
+//! # Attributes
+//!
+//! Trees accept `doc`, `derive`, `cfg` and `allow` attributes. Documentation
+//! and derives apply to the entry point, while `cfg` and `allow` apply to
+//! every generated item. Locks accept `doc` and `cfg` attributes. The
+//! documentation of a lock is appended to the one generated for its accessors,
+//! so public trees build under `#![deny(missing_docs)]` as long as their entry
+//! point is documented. A `cfg` on a lock removes its field, its constructor
+//! argument, its accessors and its forward locktree:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   /// Locks for the whole application.
+//!   #[derive(Debug)]
+//!   Main {
+//!     /// Always taken first.
+//!     first: Mutex<String>,
+//!     #[cfg(feature = "stats")]
+//!     stats: Mutex<u64>,
+//!   }
+//! }
+//! # #[cfg(not(feature = "stats"))]
+//! let locks = MainLockTree::new(String::new());
+//! ```
+//!
+//! Locks under different `cfg` attributes may share a name, so that each
+//! configuration picks one of them. Locks under the same `cfg` attributes
+//! can't, and whether two different sets of attributes actually exclude each
+//! other is left to the compiler.
+//!
+//! # Generics
+//!
+//! Trees can have generic parameters and a where clause, which are carried to
+//! the entry point and to every forward locktree:
+//!
+//! ```
+//! # use locktree::locktree;
+//! # use std::collections::HashMap;
+//! # use std::hash::Hash;
+//! locktree! {
+//!   Cache<K, V> where K: Eq + Hash {
+//!     index: RwLock<HashMap<K, V>>,
+//!   }
+//! }
+//!
+//! let cache = CacheLockTree::new(HashMap::from([(1, "one")]));
+//! let (index, _forward) = cache.read_index();
+//! assert_eq!(index.get(&1), Some(&"one"));
+//! ```
+//!
+//! Parameters used in locks must satisfy the bounds of the lock
+//! implementations. Lifetime parameters are allowed too, so trees can lock
+//! borrowed data, although the lifetimes `'a`, `'b` and `'x` are reserved for
+//! the generated code:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   Buffers<'buf> {
+//!     input: Mutex<&'buf mut Vec<u8>>,
+//!     output: Mutex<&'buf mut Vec<u8>>,
+//!   }
+//! }
+//!
+//! let (mut input, mut output) = (vec![1, 2, 3], Vec::new());
+//! let buffers = BuffersLockTree::new(&mut input, &mut output);
+//! std::thread::scope(|scope| {
+//!   scope.spawn(|| {
+//!     let (input, mut forward) = buffers.lock_input();
+//!     let (mut output, _) = forward.lock_output();
+//!     output.extend_from_slice(&input);
+//!   });
+//! });
+//! drop(buffers);
+//! assert_eq!(output, [1, 2, 3]);
+//! ```
+//!
+//! # Non-Blocking Locking
+//!
+//! Every `lock_x`, `read_x` and `write_x` accessor has a `try_lock_x`,
+//! `try_read_x` or `try_write_x` counterpart which returns `None` instead of
+//! blocking when the lock is already held:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   Main {
+//!     first: Mutex<String>,
+//!     second: RwLock<Vec<usize>>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), Vec::new());
+//! let (_first, mut forward) = locks.try_lock_first().unwrap();
+//! assert!(locks.try_lock_first().is_none());
+//! let _second = forward.try_write_second().unwrap();
+//! ```
+//!
+//! Try accessors of `async` locks return their guard right away rather than a
+//! future. Custom locks must implement the `try_*` methods of their traits.
+//!
+//! ## Out-of-Order Locking
+//!
+//! Since try accessors never wait, they can't be part of a deadlock cycle.
+//! Forward locktrees use this to expose `try_lock_x_out_of_order`,
+//! `try_read_x_out_of_order` and `try_write_x_out_of_order` for every lock that
+//! does *not* come after their own, including earlier locks and locks in
+//! sibling branches:
+//!
+//! ```
+//! # use locktree::{locktree, WouldBlock};
+//! locktree! {
+//!   Main {
+//!     first: Mutex<String>,
+//!     second: RwLock<Vec<usize>>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), Vec::new());
+//! let (_second, forward) = locks.read_second();
+//! let start_over = match forward.try_lock_first_out_of_order() {
+//!   Ok(_first) => false,
+//!   // Drop everything and lock `first` before `second` instead
+//!   Err(WouldBlock) => true,
+//! };
+//! ```
+//!
+//! These return only the guard, or `WouldBlock` if the lock is already held.
+//! The guard borrows the forward locktree, so nothing else can be locked
+//! through it until the guard is dropped.
+//!
+//! # Timeouts
+//!
+//! Every `lock_x`, `read_x` and `write_x` accessor also has `_for` and
+//! `_until` variants taking a `Duration` or an `Instant`, respectively. They
+//! return `TimedOut` if the lock couldn't be acquired in time:
+//!
+//! ```
+//! # use locktree::{locktree, TimedOut};
+//! # use std::time::Duration;
+//! locktree! {
+//!   Main {
+//!     first: Mutex<String>,
+//!     second: RwLock<Vec<usize>>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), Vec::new());
+//! let timeout = Duration::from_millis(10);
+//! let (_first, mut forward) = locks.lock_first_for(timeout).unwrap();
+//! assert_eq!(locks.lock_first_for(timeout).err(), Some(TimedOut));
+//! let _second = forward.write_second_for(timeout).unwrap();
+//! ```
+//!
+//! By default, timed locking is implemented on top of the `try_*` methods of
+//! the lock traits, polling until the lock is acquired or the deadline is
+//! reached. Locks with native support for timeouts override this.
+//!
+//! For `async` locks, the returned future resolves to a `Result` instead.
+//! Polling would keep the executor busy, so these accessors and the deadline
+//! ones below only exist for locks implementing `AsyncTimedMutex` or
+//! `AsyncTimedRwLock`: `tokio`'s use `tokio::time`, and the other built-in
+//! backends use `futures-timer`.
+//!
+//! ## Deadlines
+//!
+//! A single deadline can also be shared by a whole chain of locks. Entry
+//! points have `lock_x_with_deadline`, `read_x_with_deadline` and
+//! `write_x_with_deadline` accessors which return a forward locktree keeping
+//! that deadline. Every blocking accessor of such a forward locktree waits
+//! until that same deadline, returns a `Result`, and passes the deadline
+//! along:
+//!
+//! ```
+//! # use locktree::locktree;
+//! # use std::sync::Barrier;
+//! # use std::thread;
+//! # use std::time::{Duration, Instant};
+//! locktree! {
+//!   Main {
+//!     first: Mutex<String>,
+//!     second: RwLock<Vec<usize>>,
+//!     third: Mutex<u64>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), Vec::new(), 0);
+//! let barrier = Barrier::new(2);
+//! thread::scope(|scope| {
+//!   // Another thread holds `third` for a while
+//!   scope.spawn(|| {
+//!     let (_third, _) = locks.lock_third();
+//!     barrier.wait();
+//!     barrier.wait();
+//!   });
+//!   barrier.wait();
+//!
+//!   let deadline = Instant::now() + Duration::from_millis(10);
+//!   let (_first, mut forward) =
+//!     locks.lock_first_with_deadline(deadline).unwrap();
+//!   let (_second, mut forward) = forward.read_second().unwrap();
+//!   let error = forward.lock_third().err().unwrap();
+//!   assert_eq!(error.lock(), "third");
+//!   barrier.wait();
+//! });
+//! ```
+//!
+//! Once the deadline is reached, these return a `DeadlineExceeded` with the
+//! name of the lock that couldn't be acquired. `WithDeadline::with_deadline`
+//! gives a whole entry point a deadline, and nested trees reached from a
+//! forward locktree with a deadline keep it. Upgrades can't give up waiting,
+//! so forward locktrees with a deadline have no `upgrade_x` accessors, and
+//! no `upgradable_read_x` ones for `async` locks.
+//!
+//! # Locking Several Locks at Once
+//!
+//! Forward locktrees also have consuming accessors, prefixed with `into_`,
+//! which keep the lifetime of the forward they were called on. `lock_many!`
+//! uses them to take any ordered subset of a tree's locks in a single
+//! expression. Its first argument is a call to any accessor, followed by the
+//! names of the accessors for the next locks:
+//!
+//! ```
+//! # use locktree::{lock_many, locktree};
+//! locktree! {
+//!   Main {
+//!     first: Mutex<String>,
+//!     second: Mutex<u64>,
+//!     third: RwLock<Vec<usize>>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), 0, Vec::new());
+//! let ((_first, _third), _forward) =
+//!   lock_many!(locks.lock_first(), write_third);
+//! ```
+//!
+//! This returns a tuple of guards along with the forward locktree of the last
+//! lock. Since each step goes through the regular accessors, locks must still
+//! be listed in order. For `async` locks the guards are futures which should
+//! be awaited in order as well.
+//!
+//! # Poisoning
+//!
+//! By default, `Mutex` and `RwLock` locks use the standard library's locks,
+//! and locking them panics if a previous holder panicked. A `poison`
+//! attribute on a tree, or on a single lock, picks another policy:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   #[poison(recover)]
+//!   Main {
+//!     first: Mutex<String>,
+//!     #[poison(propagate)]
+//!     second: RwLock<Vec<usize>>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), Vec::new());
+//! let (_first, mut forward) = locks.lock_first();
+//! let (second, _) = forward.read_second();
+//! assert!(second.is_ok());
+//! ```
+//!
+//! `recover` ignores poisoning and returns the guard anyway, while
+//! `propagate` returns the `LockResult` to the caller. `panic` restores the
+//! default. Locks with a `poison` attribute, or in a tree with one, also have
+//! a `clear_poison_x` accessor on the main locktree. Policies only apply to
+//! locks without an explicit HKT, and are otherwise up to the lock
+//! implementation.
+//!
+//! # Exclusive Trees
+//!
+//! Entry points take `&self`, so nothing stops a thread from entering the same
+//! tree twice and deadlocking on itself. Prefixing a tree with `exclusive`
+//! (after the visibility, if any) makes every entry accessor also take a
+//! `&mut LockToken`:
+//!
+//! ```
+//! # use locktree::{locktree, LockToken};
+//! locktree! {
+//!   exclusive Main {
+//!     first: Mutex<String>,
+//!     second: RwLock<Vec<usize>>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new(), Vec::new());
+//! let mut token = LockToken::acquire().unwrap();
+//! let (_first, mut forward) = locks.lock_first(&mut token);
+//! let _second = forward.read_second();
+//! ```
+//!
+//! There is at most one token per thread, and it stays mutably borrowed by the
+//! forward locktrees for as long as they are alive. Thus a thread can only be
+//! inside of a single `exclusive` tree at a time. Tokens are not `Send`, so
+//! they are best suited for synchronous code or single-threaded executors.
 //!
 //! # Composing
 //!
-//! TODO
+//! A tree can be embedded into another one as a `Tree` entry, taking the type
+//! of the inner tree's entry point:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   Storage {
+//!     index: RwLock<Vec<usize>>,
+//!     files: Mutex<Vec<String>>,
+//!   }
+//!
+//!   Main {
+//!     config: RwLock<String>,
+//!     storage: Tree<StorageLockTree>,
+//!     stats: Mutex<u64>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(
+//!   String::new(),
+//!   StorageLockTree::new(Vec::new(), Vec::new()),
+//!   0,
+//! );
+//! let (_config, mut forward) = locks.read_config();
+//! let mut forward = forward.enter_storage();
+//! let (_index, _) = forward.storage().read_index();
+//! ```
+//!
+//! Instead of locking, `enter_storage` only returns a forward locktree, which
+//! gives access to the inner tree through `storage`. The inner tree borrows
+//! the forward, so none of the outer locks can be taken while holding an inner
+//! guard, and no inner lock can be taken while holding `stats`. Once the inner
+//! guards are dropped, `stats` can be locked through the same forward.
+//!
+//! Note that the inner tree is entered through its regular entry point. An
+//! `exclusive` tree can't be usefully embedded into another `exclusive` tree,
+//! since the outer forward locktrees hold on to the only `LockToken`.
+//!
+//! # Shared Fields
+//!
+//! Data that never needs locking, like configuration or atomics, can be stored
+//! in the tree as a `Shared` entry:
+//!
+//! ```
+//! # use locktree::locktree;
+//! # use std::sync::atomic::{AtomicUsize, Ordering};
+//! locktree! {
+//!   Main {
+//!     limit: Shared<usize>,
+//!     queue: Mutex<Vec<usize>>,
+//!     hits: Shared<AtomicUsize>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(16, Vec::new(), AtomicUsize::new(0));
+//! let (mut queue, forward) = locks.lock_queue();
+//! if queue.len() < *forward.limit() {
+//!   queue.push(0);
+//! }
+//! forward.hits().fetch_add(1, Ordering::Relaxed);
+//! ```
+//!
+//! The `limit` and `hits` functions return a plain reference. They are
+//! available on the entry point and on every forward locktree, whatever the
+//! position of the field, and don't take part in the lock order. Shared fields
+//! have no forward locktree of their own and can't have branches. Since their
+//! accessor is named after the field itself, a shared field can't take the
+//! name of a function generated for another lock, such as `lock_queue`.
+//!
+//! # Construction
+//!
+//! `new` takes the value of every lock in declaration order, branches
+//! included. With the `#[builder]` attribute, the tree also gets a builder,
+//! named `MainLockTreeBuilder` here, with a setter per lock. Locks can be given
+//! a default value, which is used when their setter isn't called:
+//!
+//! ```
+//! # use locktree::locktree;
+//! locktree! {
+//!   #[builder]
+//!   Main {
+//!     name: Mutex<String>,
+//!     counter: Mutex<u64> = 0,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::builder().name("main".to_string()).build();
+//! assert_eq!(*locks.unwrap().lock_counter().0, 0);
+//! let error = MainLockTree::builder().build().err().unwrap();
+//! assert_eq!(error.lock(), "name");
+//! ```
+//!
+//! `build` returns a `MissingValue` error if a lock without a default value
+//! wasn't set. When every lock has a default value, `MainLockTree` also
+//! implements `Default`, so it can't be derived. Default values are evaluated
+//! each time they are used. Like in `if` conditions, a struct literal must be
+//! wrapped in parentheses when used as a default value.
+//!
+//! The builder is opt-in since it adds a struct and two functions to the tree,
+//! and takes the names `build` and `builder`, which can't be used as lock
+//! names in trees with `#[builder]`. Trees without it can use them freely. The
+//! names `locks`, `new` and `token` are always reserved.
+//!
+//! # `parking_lot`
+//!
+//! With the `parking_lot` feature, `parking_lot`'s `Mutex`, `FairMutex`,
+//! `ReentrantMutex` and `RwLock` implement this crate's lock traits. They can
+//! be used through the `Mutex(parking_lot)`, `Mutex(parking_lot_fair)`,
+//! `Mutex(parking_lot_reentrant)` and `RwLock(parking_lot)` shorthands, which
+//! don't require a direct dependency on `parking_lot`. Timed accessors use
+//! `parking_lot`'s own timeouts instead of polling.
+//!
+//! # Async Backends
+//!
+//! Besides `tokio`, `async` locks can come from `async-lock`, `async-std` and
+//! `futures`, each behind a feature of the same name. `async-std` re-exports
+//! the locks of `async-lock`, so its feature enables `async-lock` as well.
+//! Again, there are shorthands: `async Mutex(async_lock)` and
+//! `async RwLock(async_lock)`, their `async_std` equivalents, and
+//! `async Mutex(futures)`. None of these backends depends on a particular
+//! executor, and neither do their timed accessors.
+//!
+//! The futures returned by `async` accessors are `Send` whenever the lock's
+//! own futures are, so they can be awaited in tasks spawned on multi-threaded
+//! executors. For the built-in backends, this is the case when the protected
+//! value is `Send` (and `Sync` as well, for `RwLock`s). Locks protecting other
+//! values, such as an `Rc`, can still be used on single-threaded executors.
+//!
+//! `async` accessors return the lock's own futures as `impl Future`, so
+//! locking never allocates, including for timed and deadline accessors.
+//!
+//! # Downgrading
+//!
+//! The forward locktree of an `RwLock` can turn the write guard returned
+//! along with it into a read guard, without releasing the lock. Other readers
+//! can then get in, while the forward remains usable for the following locks.
+//! This is only available for locks implementing `DowngradableRwLock`, such
+//! as `parking_lot`'s, but not the standard library's:
+//!
+//! ```
+//! # #[cfg(feature = "parking_lot")]
+//! # mod example {
+//! # use locktree::locktree;
+//! locktree! {
+//!   Main {
+//!     config: RwLock(parking_lot)<String>,
+//!     stats: Mutex<u64>,
+//!   }
+//! }
+//!
+//! fn configure(locks: &MainLockTree) {
+//!   let (mut config, mut forward) = locks.write_config();
+//!   config.push_str("verbose");
+//!   let config = forward.downgrade_config(config);
+//!   let (mut stats, _) = forward.lock_stats();
+//!   *stats += config.len() as u64;
+//! }
+//! # }
+//! ```
+//!
+//! `downgrade_x` is also available on `async` locks implementing
+//! `AsyncDowngradableRwLock`, such as `tokio`'s and `async-lock`'s, where it
+//! doesn't need to be awaited.
+//!
+//! # Mapped Guards
+//!
+//! Guards can be narrowed to a part of the locked data, so that only that part
+//! is handed out. Both the entry point and the lock's own forward locktree
+//! have `map_x` and `try_map_x` functions for `Mutex`es, and `map_read_x`,
+//! `map_write_x` and their `try_` counterparts for `RwLock`s:
+//!
+//! ```
+//! # use locktree::{locktree, MappedGuard};
+//! struct User {
+//!   name: String,
+//!   password: String,
+//! }
+//!
+//! locktree! {
+//!   Main {
+//!     user: Mutex<User>,
+//!   }
+//! }
+//!
+//! fn user_name(locks: &MainLockTree) -> MappedGuard<'_, String> {
+//!   let (user, forward) = locks.lock_user();
+//!   forward.map_user(user, |user| &mut user.name)
+//! }
+//! ```
+//!
+//! They are available for locks implementing the `MappableMutex` and
+//! `MappableRwLock` traits, such as the standard library locks and
+//! `parking_lot`'s, or their `async` equivalents, such as `tokio`'s. The
+//! entry point's functions take guards returned by the entry point, while the
+//! forward's take the guard returned along with it.
+//!
+//! The guards of the standard library locks can't be mapped on stable Rust,
+//! so they are boxed into a `MappedGuard` or `MappedReadGuard`. The other
+//! backends use their own mapped guard types.
+//!
+//! # Upgradable Reads
+//!
+//! `UpgradableRwLock`s can also be locked for an upgradable read, which is
+//! shared with plain reads but not with writes or other upgradable reads. Its
+//! guard can then be turned into a write guard through the forward locktree
+//! returned along with it, without releasing the lock in between. Borrowing
+//! the forward makes sure no following lock is held during the upgrade.
+//!
+//! There is no default HKT for upgradable locks: use `parking_lot`'s with
+//! `UpgradableRwLock(parking_lot)`, or `async-lock`'s with
+//! `async UpgradableRwLock(async_lock)`. Other locks can implement
+//! `UpgradableRwLock` or `AsyncUpgradableRwLock`.
+//!
+//! ```
+//! # #[cfg(feature = "parking_lot")]
+//! # mod example {
+//! # use locktree::locktree;
+//! locktree! {
+//!   Main {
+//!     cache: UpgradableRwLock(parking_lot)<Option<u32>>,
+//!   }
+//! }
+//!
+//! fn get_or_compute(locks: &MainLockTree) -> u32 {
+//!   let (cache, mut forward) = locks.upgradable_read_cache();
+//!   match *cache {
+//!     Some(value) => value,
+//!     None => {
+//!       let mut cache = forward.upgrade_cache(cache);
+//!       *cache.insert(42)
+//!     }
+//!   }
+//! }
+//! # }
+//! ```
+//!
+//! # Custom Locks
+//!
+//! Any other lock can be used through an explicit HKT once it implements
+//! `New` and one of the lock traits. `backend!` generates both from the lock
+//! type and its guard types, forwarding to the lock's own methods:
+//!
+//! ```
+//! # use locktree::{backend, locktree};
+//! # use std::sync::{RwLockReadGuard, RwLockWriteGuard};
+//! struct MyLock<T>(std::sync::RwLock<T>);
+//!
+//! impl<T> MyLock<T> {
+//!   fn new(value: T) -> Self {
+//!     Self(std::sync::RwLock::new(value))
+//!   }
+//!
+//!   fn shared(&self) -> RwLockReadGuard<'_, T> {
+//!     self.0.read().unwrap()
+//!   }
+//!
+//!   fn exclusive(&self) -> RwLockWriteGuard<'_, T> {
+//!     self.0.write().unwrap()
+//!   }
+//!
+//!   fn try_shared(&self) -> Option<RwLockReadGuard<'_, T>> {
+//!     self.0.try_read().ok()
+//!   }
+//!
+//!   fn try_exclusive(&self) -> Option<RwLockWriteGuard<'_, T>> {
+//!     self.0.try_write().ok()
+//!   }
+//! }
+//!
+//! backend! {
+//!   impl<T> RwLock for MyLock<T> {
+//!     type ReadGuard<'a> = RwLockReadGuard<'a, T>;
+//!     type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+//!
+//!     fn read = shared;
+//!     fn write = exclusive;
+//!     fn try_read = try_shared;
+//!     fn try_write = try_exclusive;
+//!   }
+//! }
+//!
+//! locktree! {
+//!   Main {
+//!     lock: RwLock(MyLock)<String>,
+//!   }
+//! }
+//!
+//! let locks = MainLockTree::new(String::new());
+//! let (_lock, _) = locks.read_lock();
+//! ```
+//!
+//! The implemented trait is one of `Mutex`, `RwLock`, `AsyncMutex` or
+//! `AsyncRwLock`, and the locked data must be the last generic argument of the
+//! lock type. Methods default to the trait's names, and must be inherent
+//! methods of the lock: a missing one is reported as multiple applicable items,
+//! instead of the lock trait calling itself forever. Non-blocking methods may
+//! return either an `Option` or a `Result`. For `async` locks, lock methods may
+//! be `async fn`s or return any future resolving to the guard. Given timed
+//! variants, as in `fn lock_until = acquire_before;`, they also implement
+//! `AsyncTimedMutex` or `AsyncTimedRwLock`. These take an `Instant` and return
+//! a future resolving to an `Option` or a `Result`. Timed variants that are not
+//! given default to the lock method's name with an `_until` suffix. `RwLock`s
+//! that can downgrade write guards also implement `DowngradableRwLock` or
+//! `AsyncDowngradableRwLock` when given a downgrading function, as in
+//! `fn downgrade = MyWriteGuard::into_read;`.
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::sync::TryLockError;
+use std::time::{Duration, Instant};
+
+/// `locktree!` macro. See the module-level documentation for details.
+pub use locktree_derive::locktree;
+
+/// `lock_many!` macro. See the module-level documentation for details.
+pub use locktree_derive::lock_many;
+
+/// `backend!` macro. See the module-level documentation for details.
+pub use locktree_derive::backend;
+
+pub use crate::map::{MappedGuard, MappedReadGuard};
+pub use crate::poison::ClearPoison;
+pub use crate::token::LockToken;
+#[cfg(feature = "async-lock")]
+#[doc(hidden)]
+pub use async_lock;
+#[cfg(feature = "async-std")]
+#[doc(hidden)]
+pub use async_std;
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+pub use futures;
+#[cfg(feature = "parking_lot")]
+#[doc(hidden)]
+pub use parking_lot;
+
+#[doc(hidden)]
+pub mod backend;
+#[doc(hidden)]
+pub mod deadline;
+mod map;
+pub mod poison;
+mod timeout;
+mod token;
+
+pub type PluggedMutexGuard<'a, T> = <T as Mutex>::Guard<'a>;
+
+pub type PluggedRwLockReadGuard<'a, T> = <T as RwLock>::ReadGuard<'a>;
+
+pub type PluggedRwLockWriteGuard<'a, T> = <T as RwLock>::WriteGuard<'a>;
+
+pub type PluggedRwLockUpgradableReadGuard<'a, T> =
+    <T as UpgradableRwLock>::UpgradableGuard<'a>;
+
+/// Error returned by out-of-order accessors when the lock is already held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WouldBlock;
+
+impl fmt::Display for WouldBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("lock is already held")
+    }
+}
+
+impl Error for WouldBlock {}
+
+/// Error returned by timed accessors when the lock could not be acquired in
+/// time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out waiting for lock")
+    }
+}
+
+impl Error for TimedOut {}
+
+/// Error returned by accessors waiting until the deadline of a chain of locks
+/// when it is reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeadlineExceeded {
+    lock: &'static str,
+}
+
+impl DeadlineExceeded {
+    /// Name of the lock that couldn't be acquired in time.
+    pub fn lock(&self) -> &'static str {
+        self.lock
+    }
+}
+
+impl fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline exceeded while waiting for `{}`", self.lock)
+    }
+}
+
+impl Error for DeadlineExceeded {}
+
+/// Locktrees whose entry point can be given a deadline, implemented by
+/// `locktree!`. Forward locktrees pass their own deadline into nested trees
+/// through it.
+pub trait WithDeadline {
+    type Deadline<'a>
+    where
+        Self: 'a;
+
+    /// Entry point whose accessors give up at `deadline`, and return forward
+    /// locktrees keeping it.
+    fn with_deadline(&self, deadline: Instant) -> Self::Deadline<'_>;
+}
+
+/// Error returned by the `build` function of a builder when a lock without a
+/// default value wasn't set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MissingValue {
+    lock: &'static str,
+}
+
+impl MissingValue {
+    #[doc(hidden)]
+    pub fn new(lock: &'static str) -> Self {
+        Self { lock }
+    }
+
+    /// Name of the lock that wasn't set.
+    pub fn lock(&self) -> &'static str {
+        self.lock
+    }
+}
+
+impl fmt::Display for MissingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing value for `{}`", self.lock)
+    }
+}
+
+impl Error for MissingValue {}
+
+pub trait New<T> {
+    fn new(value: T) -> Self;
+}
+
+impl<T> New<T> for ::std::sync::Mutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> New<T> for ::std::sync::RwLock<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> New<T> for ::tokio::sync::Mutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> New<T> for ::tokio::sync::RwLock<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "async-lock")]
+impl<T> New<T> for ::async_lock::Mutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "async-lock")]
+impl<T> New<T> for ::async_lock::RwLock<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> New<T> for ::futures::lock::Mutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> New<T> for ::parking_lot::Mutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> New<T> for ::parking_lot::FairMutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> New<T> for ::parking_lot::ReentrantMutex<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> New<T> for ::parking_lot::RwLock<T> {
+    fn new(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+pub trait Mutex {
+    type Guard<'a>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Self::Guard<'_>;
+
+    /// Lock without blocking, returning `None` if the lock is already held.
+    fn try_lock(&self) -> Option<Self::Guard<'_>>;
+
+    /// Lock, giving up once `deadline` is reached.
+    ///
+    /// The default implementation polls `try_lock`. Locks with native support
+    /// for timeouts should override it.
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::Guard<'_>, TimedOut> {
+        timeout::poll_until(deadline, || self.try_lock())
+    }
+
+    /// Lock, giving up after `timeout`.
+    fn lock_for(&self, timeout: Duration) -> Result<Self::Guard<'_>, TimedOut> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.lock_until(deadline),
+            None => Ok(self.lock()),
+        }
+    }
+}
+
+impl<T> Mutex for std::sync::Mutex<T> {
+    type Guard<'a>
+        = std::sync::MutexGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Self::Guard<'_> {
+        std::sync::Mutex::<T>::lock(self).unwrap()
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        match std::sync::Mutex::<T>::try_lock(self) {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::WouldBlock) => None,
+            Err(error @ TryLockError::Poisoned(_)) => panic!("{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> Mutex for parking_lot::Mutex<T> {
+    type Guard<'a>
+        = parking_lot::MutexGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Self::Guard<'_> {
+        parking_lot::Mutex::<T>::lock(self)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        parking_lot::Mutex::<T>::try_lock(self)
+    }
+
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::Guard<'_>, TimedOut> {
+        parking_lot::Mutex::<T>::try_lock_until(self, deadline).ok_or(TimedOut)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> Mutex for parking_lot::FairMutex<T> {
+    type Guard<'a>
+        = parking_lot::FairMutexGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Self::Guard<'_> {
+        parking_lot::FairMutex::<T>::lock(self)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        parking_lot::FairMutex::<T>::try_lock(self)
+    }
+
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::Guard<'_>, TimedOut> {
+        parking_lot::FairMutex::<T>::try_lock_until(self, deadline)
+            .ok_or(TimedOut)
+    }
+}
 
-use crate::plug::*;
-#[cfg(feature = "async")]
-use std::future::Future;
-use std::pin::Pin;
+#[cfg(feature = "parking_lot")]
+impl<T> Mutex for parking_lot::ReentrantMutex<T> {
+    type Guard<'a>
+        = parking_lot::ReentrantMutexGuard<'a, T>
+    where
+        Self: 'a;
 
-/// `locktree!` macro. See the module-level documentation for details.
-pub use locktree_derive::locktree;
+    fn lock(&self) -> Self::Guard<'_> {
+        parking_lot::ReentrantMutex::<T>::lock(self)
+    }
 
-pub mod plug;
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        parking_lot::ReentrantMutex::<T>::try_lock(self)
+    }
 
-pub type PluggedGuard<'a, T> = <T as PlugLifetime<'a>>::Type;
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::Guard<'_>, TimedOut> {
+        parking_lot::ReentrantMutex::<T>::try_lock_until(self, deadline)
+            .ok_or(TimedOut)
+    }
+}
 
-pub type PluggedMutexGuard<'a, T> = PluggedGuard<'a, <T as Mutex>::Guard>;
+/// `Mutex`es whose guards can be narrowed to a part of the locked data.
+pub trait MappableMutex: Mutex {
+    /// Type of the locked data.
+    type Target: ?Sized;
+    type MappedGuard<'a, U: ?Sized + 'a>
+    where
+        Self: 'a;
 
-pub type PluggedRwLockReadGuard<'a, T> =
-    PluggedGuard<'a, <T as RwLock>::ReadGuard>;
+    /// Narrow `guard` to the part of the locked data returned by `f`. The
+    /// lock stays held until the mapped guard is dropped.
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> &mut U;
 
-pub type PluggedRwLockWriteGuard<'a, T> =
-    PluggedGuard<'a, <T as RwLock>::WriteGuard>;
+    /// Like `map`, but gives `guard` back if `f` returns `None`.
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> Option<&mut U>;
+}
 
-#[cfg(feature = "async")]
-pub type PluggedAsyncGuard<'a, T> =
-    Pin<Box<dyn Future<Output = <T as PlugLifetime<'a>>::Type> + 'a>>;
+impl<T> MappableMutex for std::sync::Mutex<T> {
+    type Target = T;
+    type MappedGuard<'a, U: ?Sized + 'a>
+        = MappedGuard<'a, U>
+    where
+        Self: 'a;
 
-#[cfg(feature = "async")]
-pub type PluggedAsyncMutexGuard<'a, T> =
-    PluggedAsyncGuard<'a, <T as AsyncMutex>::Guard>;
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MappedGuard::new(guard, f)
+    }
 
-#[cfg(feature = "async")]
-pub type PluggedAsyncRwLockReadGuard<'a, T> =
-    PluggedAsyncGuard<'a, <T as AsyncRwLock>::ReadGuard>;
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        MappedGuard::try_new(guard, f)
+    }
+}
 
-#[cfg(feature = "async")]
-pub type PluggedAsyncRwLockWriteGuard<'a, T> =
-    PluggedAsyncGuard<'a, <T as AsyncRwLock>::WriteGuard>;
+#[cfg(feature = "parking_lot")]
+impl<T> MappableMutex for parking_lot::Mutex<T> {
+    type Target = T;
+    type MappedGuard<'a, U: ?Sized + 'a>
+        = parking_lot::MappedMutexGuard<'a, U>
+    where
+        Self: 'a;
 
-pub trait New<T> {
-    fn new(value: T) -> Self;
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        parking_lot::MutexGuard::map(guard, f)
+    }
+
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        parking_lot::MutexGuard::try_map(guard, f)
+    }
 }
 
-impl<T> New<T> for ::std::sync::Mutex<T> {
-    fn new(value: T) -> Self {
-        Self::new(value)
+#[cfg(feature = "parking_lot")]
+impl<T> MappableMutex for parking_lot::FairMutex<T> {
+    type Target = T;
+    type MappedGuard<'a, U: ?Sized + 'a>
+        = parking_lot::MappedFairMutexGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        parking_lot::FairMutexGuard::map(guard, f)
+    }
+
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        parking_lot::FairMutexGuard::try_map(guard, f)
     }
 }
 
-impl<T> New<T> for ::std::sync::RwLock<T> {
-    fn new(value: T) -> Self {
-        Self::new(value)
+#[cfg(feature = "async")]
+pub trait AsyncMutex {
+    type Guard<'a>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> impl Future<Output = Self::Guard<'_>> + '_;
+
+    /// Lock without waiting, returning `None` if the lock is already held.
+    fn try_lock(&self) -> Option<Self::Guard<'_>>;
+}
+
+#[cfg(feature = "async")]
+/// `AsyncMutex`es that can give up waiting once a deadline is reached.
+/// Polling would keep the executor busy, so this is left to locks with access
+/// to a timer.
+pub trait AsyncTimedMutex: AsyncMutex {
+    /// Lock, giving up once `deadline` is reached.
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::Guard<'_>, TimedOut>> + '_;
+
+    /// Lock, giving up after `timeout`.
+    fn lock_for(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<Self::Guard<'_>, TimedOut>> + '_ {
+        let deadline = Instant::now().checked_add(timeout);
+        async move {
+            match deadline {
+                Some(deadline) => self.lock_until(deadline).await,
+                None => Ok(self.lock().await),
+            }
+        }
     }
 }
 
 #[cfg(feature = "tokio")]
-impl<T> New<T> for ::tokio::sync::Mutex<T> {
-    fn new(value: T) -> Self {
-        Self::new(value)
+impl<T> AsyncMutex for tokio::sync::Mutex<T> {
+    type Guard<'a>
+        = tokio::sync::MutexGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> impl Future<Output = Self::Guard<'_>> + '_ {
+        tokio::sync::Mutex::<T>::lock(self)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        tokio::sync::Mutex::<T>::try_lock(self).ok()
     }
 }
 
 #[cfg(feature = "tokio")]
-impl<T> New<T> for ::tokio::sync::RwLock<T> {
-    fn new(value: T) -> Self {
-        Self::new(value)
+impl<T> AsyncTimedMutex for tokio::sync::Mutex<T> {
+    async fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::Guard<'_>, TimedOut> {
+        tokio::time::timeout_at(
+            deadline.into(),
+            tokio::sync::Mutex::<T>::lock(self),
+        )
+        .await
+        .map_err(|_| TimedOut)
     }
 }
 
-pub trait Mutex {
-    type Guard: for<'a> PlugLifetime<'a>;
+#[cfg(feature = "async-lock")]
+impl<T> AsyncMutex for async_lock::Mutex<T> {
+    type Guard<'a>
+        = async_lock::MutexGuard<'a, T>
+    where
+        Self: 'a;
 
-    fn lock(&self) -> PluggedGuard<Self::Guard>;
+    fn lock(&self) -> impl Future<Output = Self::Guard<'_>> + '_ {
+        async_lock::Mutex::<T>::lock(self)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        async_lock::Mutex::<T>::try_lock(self)
+    }
 }
 
-impl<T> Mutex for std::sync::Mutex<T>
-where
-    T: 'static,
-{
-    type Guard = H1MutexLockGuard<T>;
+#[cfg(feature = "async-lock")]
+impl<T> AsyncTimedMutex for async_lock::Mutex<T> {
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::Guard<'_>, TimedOut>> + '_ {
+        timeout::timeout_at(deadline, async_lock::Mutex::<T>::lock(self))
+    }
+}
 
-    fn lock(&self) -> PluggedGuard<Self::Guard> {
-        std::sync::Mutex::<T>::lock(self).unwrap()
+#[cfg(feature = "futures")]
+impl<T> AsyncMutex for futures::lock::Mutex<T> {
+    type Guard<'a>
+        = futures::lock::MutexGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> impl Future<Output = Self::Guard<'_>> + '_ {
+        futures::lock::Mutex::<T>::lock(self)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        futures::lock::Mutex::<T>::try_lock(self)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> AsyncTimedMutex for futures::lock::Mutex<T> {
+    fn lock_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::Guard<'_>, TimedOut>> + '_ {
+        timeout::timeout_at(deadline, futures::lock::Mutex::<T>::lock(self))
     }
 }
 
 #[cfg(feature = "async")]
-pub trait AsyncMutex {
-    type Guard: for<'a> PlugLifetime<'a>;
+/// `AsyncMutex`es whose guards can be narrowed to a part of the locked data.
+pub trait AsyncMappableMutex: AsyncMutex {
+    /// Type of the locked data.
+    type Target: ?Sized;
+    type MappedGuard<'a, U: ?Sized + 'a>
+    where
+        Self: 'a;
+
+    /// Narrow `guard` to the part of the locked data returned by `f`. The
+    /// lock stays held until the mapped guard is dropped.
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> &mut U;
 
-    fn lock(&self) -> PluggedAsyncGuard<Self::Guard>;
+    /// Like `map`, but gives `guard` back if `f` returns `None`.
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> Option<&mut U>;
 }
 
 #[cfg(feature = "tokio")]
-impl<T> AsyncMutex for tokio::sync::Mutex<T>
-where
-    T: 'static,
-{
-    type Guard = H1TokioMutexLockGuard<T>;
+impl<T> AsyncMappableMutex for tokio::sync::Mutex<T> {
+    type Target = T;
+    type MappedGuard<'a, U: ?Sized + 'a>
+        = tokio::sync::MappedMutexGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        tokio::sync::MutexGuard::map(guard, f)
+    }
 
-    fn lock(&self) -> PluggedAsyncGuard<Self::Guard> {
-        Box::pin(tokio::sync::Mutex::<T>::lock(self))
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        tokio::sync::MutexGuard::try_map(guard, f)
     }
 }
 
 pub trait RwLock {
-    type ReadGuard: for<'a> PlugLifetime<'a>;
-    type WriteGuard: for<'a> PlugLifetime<'a>;
+    type ReadGuard<'a>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Self::ReadGuard<'_>;
+    fn write(&self) -> Self::WriteGuard<'_>;
+
+    /// Lock for reading without blocking, returning `None` if the lock is
+    /// already held for writing.
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>>;
+    /// Lock for writing without blocking, returning `None` if the lock is
+    /// already held.
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>>;
+
+    /// Lock for reading, giving up once `deadline` is reached.
+    ///
+    /// The default implementation polls `try_read`. Locks with native support
+    /// for timeouts should override it.
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::ReadGuard<'_>, TimedOut> {
+        timeout::poll_until(deadline, || self.try_read())
+    }
 
-    fn read(&self) -> PluggedGuard<Self::ReadGuard>;
-    fn write(&self) -> PluggedGuard<Self::WriteGuard>;
+    /// Lock for writing, giving up once `deadline` is reached.
+    ///
+    /// The default implementation polls `try_write`. Locks with native support
+    /// for timeouts should override it.
+    fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::WriteGuard<'_>, TimedOut> {
+        timeout::poll_until(deadline, || self.try_write())
+    }
+
+    /// Lock for reading, giving up after `timeout`.
+    fn read_for(
+        &self,
+        timeout: Duration,
+    ) -> Result<Self::ReadGuard<'_>, TimedOut> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.read_until(deadline),
+            None => Ok(self.read()),
+        }
+    }
+
+    /// Lock for writing, giving up after `timeout`.
+    fn write_for(
+        &self,
+        timeout: Duration,
+    ) -> Result<Self::WriteGuard<'_>, TimedOut> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.write_until(deadline),
+            None => Ok(self.write()),
+        }
+    }
 }
 
-impl<T> RwLock for std::sync::RwLock<T>
-where
-    T: 'static,
-{
-    type ReadGuard = H1RwLockReadGuard<T>;
-    type WriteGuard = H1RwLockWriteGuard<T>;
+impl<T> RwLock for std::sync::RwLock<T> {
+    type ReadGuard<'a>
+        = std::sync::RwLockReadGuard<'a, T>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+        = std::sync::RwLockWriteGuard<'a, T>
+    where
+        Self: 'a;
 
-    fn read(&self) -> PluggedGuard<Self::ReadGuard> {
+    fn read(&self) -> Self::ReadGuard<'_> {
         std::sync::RwLock::<T>::read(self).unwrap()
     }
 
-    fn write(&self) -> PluggedGuard<Self::WriteGuard> {
+    fn write(&self) -> Self::WriteGuard<'_> {
         std::sync::RwLock::<T>::write(self).unwrap()
     }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        match std::sync::RwLock::<T>::try_read(self) {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::WouldBlock) => None,
+            Err(error @ TryLockError::Poisoned(_)) => panic!("{}", error),
+        }
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        match std::sync::RwLock::<T>::try_write(self) {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::WouldBlock) => None,
+            Err(error @ TryLockError::Poisoned(_)) => panic!("{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> RwLock for parking_lot::RwLock<T> {
+    type ReadGuard<'a>
+        = parking_lot::RwLockReadGuard<'a, T>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+        = parking_lot::RwLockWriteGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Self::ReadGuard<'_> {
+        parking_lot::RwLock::<T>::read(self)
+    }
+
+    fn write(&self) -> Self::WriteGuard<'_> {
+        parking_lot::RwLock::<T>::write(self)
+    }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        parking_lot::RwLock::<T>::try_read(self)
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        parking_lot::RwLock::<T>::try_write(self)
+    }
+
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::ReadGuard<'_>, TimedOut> {
+        parking_lot::RwLock::<T>::try_read_until(self, deadline).ok_or(TimedOut)
+    }
+
+    fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::WriteGuard<'_>, TimedOut> {
+        parking_lot::RwLock::<T>::try_write_until(self, deadline)
+            .ok_or(TimedOut)
+    }
 }
 
 impl<T> RwLock for T
 where
     T: Mutex,
 {
-    type ReadGuard = T::Guard;
-    type WriteGuard = T::Guard;
+    type ReadGuard<'a>
+        = T::Guard<'a>
+    where
+        T: 'a;
+    type WriteGuard<'a>
+        = T::Guard<'a>
+    where
+        T: 'a;
 
-    fn read(&self) -> PluggedGuard<Self::ReadGuard> {
+    fn read(&self) -> Self::ReadGuard<'_> {
         self.lock()
     }
 
-    fn write(&self) -> PluggedGuard<Self::WriteGuard> {
+    fn write(&self) -> Self::WriteGuard<'_> {
         self.lock()
     }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        self.try_lock()
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        self.try_lock()
+    }
+
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::ReadGuard<'_>, TimedOut> {
+        self.lock_until(deadline)
+    }
+
+    fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::WriteGuard<'_>, TimedOut> {
+        self.lock_until(deadline)
+    }
+}
+
+/// `RwLock`s whose guards can be narrowed to a part of the locked data.
+pub trait MappableRwLock: RwLock {
+    /// Type of the locked data.
+    type Target: ?Sized;
+    type MappedReadGuard<'a, U: ?Sized + 'a>
+    where
+        Self: 'a;
+    type MappedWriteGuard<'a, U: ?Sized + 'a>
+    where
+        Self: 'a;
+
+    /// Narrow `guard` to the part of the locked data returned by `f`. The
+    /// lock stays held until the mapped guard is dropped.
+    fn map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Self::MappedReadGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&Self::Target) -> &U;
+
+    /// Like `map_read`, but gives `guard` back if `f` returns `None`.
+    fn try_map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedReadGuard<'a, U>, Self::ReadGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&Self::Target) -> Option<&U>;
+
+    /// Narrow `guard` to the part of the locked data returned by `f`. The
+    /// lock stays held until the mapped guard is dropped.
+    fn map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Self::MappedWriteGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> &mut U;
+
+    /// Like `map_write`, but gives `guard` back if `f` returns `None`.
+    fn try_map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedWriteGuard<'a, U>, Self::WriteGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> Option<&mut U>;
+}
+
+impl<T> MappableRwLock for std::sync::RwLock<T> {
+    type Target = T;
+    type MappedReadGuard<'a, U: ?Sized + 'a>
+        = MappedReadGuard<'a, U>
+    where
+        Self: 'a;
+    type MappedWriteGuard<'a, U: ?Sized + 'a>
+        = MappedGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Self::MappedReadGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> &U,
+    {
+        MappedReadGuard::new(guard, f)
+    }
+
+    fn try_map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedReadGuard<'a, U>, Self::ReadGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        MappedReadGuard::try_new(guard, f)
+    }
+
+    fn map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Self::MappedWriteGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MappedGuard::new(guard, f)
+    }
+
+    fn try_map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedWriteGuard<'a, U>, Self::WriteGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        MappedGuard::try_new(guard, f)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> MappableRwLock for parking_lot::RwLock<T> {
+    type Target = T;
+    type MappedReadGuard<'a, U: ?Sized + 'a>
+        = parking_lot::MappedRwLockReadGuard<'a, U>
+    where
+        Self: 'a;
+    type MappedWriteGuard<'a, U: ?Sized + 'a>
+        = parking_lot::MappedRwLockWriteGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Self::MappedReadGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> &U,
+    {
+        parking_lot::RwLockReadGuard::map(guard, f)
+    }
+
+    fn try_map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedReadGuard<'a, U>, Self::ReadGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        parking_lot::RwLockReadGuard::try_map(guard, f)
+    }
+
+    fn map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Self::MappedWriteGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        parking_lot::RwLockWriteGuard::map(guard, f)
+    }
+
+    fn try_map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedWriteGuard<'a, U>, Self::WriteGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        parking_lot::RwLockWriteGuard::try_map(guard, f)
+    }
+}
+
+/// `RwLock`s which can also be locked for an upgradable read.
+///
+/// An upgradable read shares the lock with plain reads but excludes writes and
+/// other upgradable reads, so it can be upgraded to a write without releasing.
+pub trait UpgradableRwLock: RwLock {
+    type UpgradableGuard<'a>
+    where
+        Self: 'a;
+
+    fn upgradable_read(&self) -> Self::UpgradableGuard<'_>;
+
+    /// Lock for an upgradable read without blocking, returning `None` if the
+    /// lock is already held for writing or for another upgradable read.
+    fn try_upgradable_read(&self) -> Option<Self::UpgradableGuard<'_>>;
+
+    /// Lock for an upgradable read, giving up once `deadline` is reached.
+    ///
+    /// The default implementation polls `try_upgradable_read`. Locks with
+    /// native support for timeouts should override it.
+    fn upgradable_read_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::UpgradableGuard<'_>, TimedOut> {
+        timeout::poll_until(deadline, || self.try_upgradable_read())
+    }
+
+    /// Atomically upgrade `guard` to a write guard, blocking until all other
+    /// readers have released the lock.
+    fn upgrade<'a>(guard: Self::UpgradableGuard<'a>) -> Self::WriteGuard<'a>
+    where
+        Self: 'a;
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> UpgradableRwLock for parking_lot::RwLock<T> {
+    type UpgradableGuard<'a>
+        = parking_lot::RwLockUpgradableReadGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn upgradable_read(&self) -> Self::UpgradableGuard<'_> {
+        parking_lot::RwLock::<T>::upgradable_read(self)
+    }
+
+    fn try_upgradable_read(&self) -> Option<Self::UpgradableGuard<'_>> {
+        parking_lot::RwLock::<T>::try_upgradable_read(self)
+    }
+
+    fn upgradable_read_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::UpgradableGuard<'_>, TimedOut> {
+        parking_lot::RwLock::<T>::try_upgradable_read_until(self, deadline)
+            .ok_or(TimedOut)
+    }
+
+    fn upgrade<'a>(guard: Self::UpgradableGuard<'a>) -> Self::WriteGuard<'a>
+    where
+        Self: 'a,
+    {
+        parking_lot::RwLockUpgradableReadGuard::upgrade(guard)
+    }
+}
+
+/// `RwLock`s whose write guards can be turned into read guards.
+pub trait DowngradableRwLock: RwLock {
+    /// Turn a write guard into a read guard without releasing the lock, so
+    /// other readers can get in but no writer.
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        Self: 'a;
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> DowngradableRwLock for parking_lot::RwLock<T> {
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        Self: 'a,
+    {
+        parking_lot::RwLockWriteGuard::downgrade(guard)
+    }
+}
+
+impl<T> DowngradableRwLock for T
+where
+    T: Mutex,
+{
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        T: 'a,
+    {
+        guard
+    }
 }
 
 #[cfg(feature = "async")]
 pub trait AsyncRwLock {
-    type ReadGuard: for<'a> PlugLifetime<'a>;
-    type WriteGuard: for<'a> PlugLifetime<'a>;
+    type ReadGuard<'a>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> impl Future<Output = Self::ReadGuard<'_>> + '_;
+    fn write(&self) -> impl Future<Output = Self::WriteGuard<'_>> + '_;
 
-    fn read(&self) -> PluggedAsyncGuard<Self::ReadGuard>;
-    fn write(&self) -> PluggedAsyncGuard<Self::WriteGuard>;
+    /// Lock for reading without waiting, returning `None` if the lock is
+    /// already held for writing.
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>>;
+    /// Lock for writing without waiting, returning `None` if the lock is
+    /// already held.
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>>;
+}
+
+#[cfg(feature = "async")]
+/// `AsyncRwLock`s that can give up waiting once a deadline is reached, like
+/// `AsyncTimedMutex`.
+pub trait AsyncTimedRwLock: AsyncRwLock {
+    /// Lock for reading, giving up once `deadline` is reached.
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::ReadGuard<'_>, TimedOut>> + '_;
+
+    /// Lock for writing, giving up once `deadline` is reached.
+    fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::WriteGuard<'_>, TimedOut>> + '_;
+
+    /// Lock for reading, giving up after `timeout`.
+    fn read_for(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<Self::ReadGuard<'_>, TimedOut>> + '_ {
+        let deadline = Instant::now().checked_add(timeout);
+        async move {
+            match deadline {
+                Some(deadline) => self.read_until(deadline).await,
+                None => Ok(self.read().await),
+            }
+        }
+    }
+
+    /// Lock for writing, giving up after `timeout`.
+    fn write_for(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<Self::WriteGuard<'_>, TimedOut>> + '_ {
+        let deadline = Instant::now().checked_add(timeout);
+        async move {
+            match deadline {
+                Some(deadline) => self.write_until(deadline).await,
+                None => Ok(self.write().await),
+            }
+        }
+    }
 }
 
 #[cfg(feature = "tokio")]
-impl<T> AsyncRwLock for tokio::sync::RwLock<T>
-where
-    T: 'static,
-{
-    type ReadGuard = H1TokioRwLockReadGuard<T>;
-    type WriteGuard = H1TokioRwLockWriteGuard<T>;
+impl<T> AsyncRwLock for tokio::sync::RwLock<T> {
+    type ReadGuard<'a>
+        = tokio::sync::RwLockReadGuard<'a, T>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+        = tokio::sync::RwLockWriteGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn read(&self) -> impl Future<Output = Self::ReadGuard<'_>> + '_ {
+        tokio::sync::RwLock::<T>::read(self)
+    }
+
+    fn write(&self) -> impl Future<Output = Self::WriteGuard<'_>> + '_ {
+        tokio::sync::RwLock::<T>::write(self)
+    }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        tokio::sync::RwLock::<T>::try_read(self).ok()
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        tokio::sync::RwLock::<T>::try_write(self).ok()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncTimedRwLock for tokio::sync::RwLock<T> {
+    async fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::ReadGuard<'_>, TimedOut> {
+        tokio::time::timeout_at(
+            deadline.into(),
+            tokio::sync::RwLock::<T>::read(self),
+        )
+        .await
+        .map_err(|_| TimedOut)
+    }
+
+    async fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> Result<Self::WriteGuard<'_>, TimedOut> {
+        tokio::time::timeout_at(
+            deadline.into(),
+            tokio::sync::RwLock::<T>::write(self),
+        )
+        .await
+        .map_err(|_| TimedOut)
+    }
+}
+
+#[cfg(feature = "async-lock")]
+impl<T> AsyncRwLock for async_lock::RwLock<T> {
+    type ReadGuard<'a>
+        = async_lock::RwLockReadGuard<'a, T>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+        = async_lock::RwLockWriteGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn read(&self) -> impl Future<Output = Self::ReadGuard<'_>> + '_ {
+        async_lock::RwLock::<T>::read(self)
+    }
+
+    fn write(&self) -> impl Future<Output = Self::WriteGuard<'_>> + '_ {
+        async_lock::RwLock::<T>::write(self)
+    }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        async_lock::RwLock::<T>::try_read(self)
+    }
 
-    fn read(&self) -> PluggedAsyncGuard<Self::ReadGuard> {
-        Box::pin(tokio::sync::RwLock::<T>::read(self))
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        async_lock::RwLock::<T>::try_write(self)
+    }
+}
+
+#[cfg(feature = "async-lock")]
+impl<T> AsyncTimedRwLock for async_lock::RwLock<T> {
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::ReadGuard<'_>, TimedOut>> + '_ {
+        timeout::timeout_at(deadline, async_lock::RwLock::<T>::read(self))
     }
 
-    fn write(&self) -> PluggedAsyncGuard<Self::WriteGuard> {
-        Box::pin(tokio::sync::RwLock::<T>::write(self))
+    fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::WriteGuard<'_>, TimedOut>> + '_ {
+        timeout::timeout_at(deadline, async_lock::RwLock::<T>::write(self))
     }
 }
 
@@ -310,14 +2067,258 @@ impl<T> AsyncRwLock for T
 where
     T: AsyncMutex,
 {
-    type ReadGuard = T::Guard;
-    type WriteGuard = T::Guard;
+    type ReadGuard<'a>
+        = T::Guard<'a>
+    where
+        T: 'a;
+    type WriteGuard<'a>
+        = T::Guard<'a>
+    where
+        T: 'a;
 
-    fn read(&self) -> PluggedAsyncGuard<Self::ReadGuard> {
+    fn read(&self) -> impl Future<Output = Self::ReadGuard<'_>> + '_ {
         self.lock()
     }
 
-    fn write(&self) -> PluggedAsyncGuard<Self::WriteGuard> {
+    fn write(&self) -> impl Future<Output = Self::WriteGuard<'_>> + '_ {
         self.lock()
     }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        self.try_lock()
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        self.try_lock()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> AsyncTimedRwLock for T
+where
+    T: AsyncTimedMutex,
+{
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::ReadGuard<'_>, TimedOut>> + '_ {
+        self.lock_until(deadline)
+    }
+
+    fn write_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<Self::WriteGuard<'_>, TimedOut>> + '_ {
+        self.lock_until(deadline)
+    }
+}
+
+#[cfg(feature = "async")]
+/// `AsyncRwLock`s whose guards can be narrowed to a part of the locked data.
+pub trait AsyncMappableRwLock: AsyncRwLock {
+    /// Type of the locked data.
+    type Target: ?Sized;
+    type MappedReadGuard<'a, U: ?Sized + 'a>
+    where
+        Self: 'a;
+    type MappedWriteGuard<'a, U: ?Sized + 'a>
+    where
+        Self: 'a;
+
+    /// Narrow `guard` to the part of the locked data returned by `f`. The
+    /// lock stays held until the mapped guard is dropped.
+    fn map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Self::MappedReadGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&Self::Target) -> &U;
+
+    /// Like `map_read`, but gives `guard` back if `f` returns `None`.
+    fn try_map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedReadGuard<'a, U>, Self::ReadGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&Self::Target) -> Option<&U>;
+
+    /// Narrow `guard` to the part of the locked data returned by `f`. The
+    /// lock stays held until the mapped guard is dropped.
+    fn map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Self::MappedWriteGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> &mut U;
+
+    /// Like `map_write`, but gives `guard` back if `f` returns `None`.
+    fn try_map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedWriteGuard<'a, U>, Self::WriteGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut Self::Target) -> Option<&mut U>;
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncMappableRwLock for tokio::sync::RwLock<T> {
+    type Target = T;
+    type MappedReadGuard<'a, U: ?Sized + 'a>
+        = tokio::sync::RwLockReadGuard<'a, U>
+    where
+        Self: 'a;
+    type MappedWriteGuard<'a, U: ?Sized + 'a>
+        = tokio::sync::RwLockMappedWriteGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Self::MappedReadGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> &U,
+    {
+        tokio::sync::RwLockReadGuard::map(guard, f)
+    }
+
+    fn try_map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedReadGuard<'a, U>, Self::ReadGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        tokio::sync::RwLockReadGuard::try_map(guard, f)
+    }
+
+    fn map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Self::MappedWriteGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        tokio::sync::RwLockWriteGuard::map(guard, f)
+    }
+
+    fn try_map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedWriteGuard<'a, U>, Self::WriteGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        tokio::sync::RwLockWriteGuard::try_map(guard, f)
+    }
+}
+
+/// `AsyncRwLock`s which can also be locked for an upgradable read.
+#[cfg(feature = "async")]
+pub trait AsyncUpgradableRwLock: AsyncRwLock {
+    type UpgradableGuard<'a>
+    where
+        Self: 'a;
+
+    fn upgradable_read(
+        &self,
+    ) -> impl Future<Output = Self::UpgradableGuard<'_>> + '_;
+
+    /// Lock for an upgradable read without waiting, returning `None` if the
+    /// lock is already held for writing or for another upgradable read.
+    fn try_upgradable_read(&self) -> Option<Self::UpgradableGuard<'_>>;
+
+    /// Atomically upgrade `guard` to a write guard, waiting until all other
+    /// readers have released the lock.
+    fn upgrade<'a>(
+        guard: Self::UpgradableGuard<'a>,
+    ) -> impl Future<Output = Self::WriteGuard<'a>> + 'a
+    where
+        Self: 'a;
+}
+
+#[cfg(feature = "async-lock")]
+impl<T> AsyncUpgradableRwLock for async_lock::RwLock<T> {
+    type UpgradableGuard<'a>
+        = async_lock::RwLockUpgradableReadGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn upgradable_read(
+        &self,
+    ) -> impl Future<Output = Self::UpgradableGuard<'_>> + '_ {
+        async_lock::RwLock::<T>::upgradable_read(self)
+    }
+
+    fn try_upgradable_read(&self) -> Option<Self::UpgradableGuard<'_>> {
+        async_lock::RwLock::<T>::try_upgradable_read(self)
+    }
+
+    fn upgrade<'a>(
+        guard: Self::UpgradableGuard<'a>,
+    ) -> impl Future<Output = Self::WriteGuard<'a>> + 'a
+    where
+        Self: 'a,
+    {
+        async_lock::RwLockUpgradableReadGuard::upgrade(guard)
+    }
+}
+
+/// `AsyncRwLock`s whose write guards can be turned into read guards.
+#[cfg(feature = "async")]
+pub trait AsyncDowngradableRwLock: AsyncRwLock {
+    /// Turn a write guard into a read guard without releasing the lock, so
+    /// other readers can get in but no writer.
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        Self: 'a;
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncDowngradableRwLock for tokio::sync::RwLock<T> {
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        Self: 'a,
+    {
+        tokio::sync::RwLockWriteGuard::downgrade(guard)
+    }
+}
+
+#[cfg(feature = "async-lock")]
+impl<T> AsyncDowngradableRwLock for async_lock::RwLock<T> {
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        Self: 'a,
+    {
+        async_lock::RwLockWriteGuard::downgrade(guard)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> AsyncDowngradableRwLock for T
+where
+    T: AsyncMutex,
+{
+    fn downgrade<'a>(guard: Self::WriteGuard<'a>) -> Self::ReadGuard<'a>
+    where
+        T: 'a,
+    {
+        guard
+    }
 }
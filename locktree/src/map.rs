@@ -0,0 +1,144 @@
+//! Mapped guards for the standard library locks, whose own guards can't be
+//! mapped on stable Rust.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// Any guard, only kept around to be dropped.
+trait Guard {}
+
+impl<T> Guard for T {}
+
+/// Guard giving mutable access to a part of the locked data.
+///
+/// The original guard is boxed and kept until this one is dropped, so the
+/// lock stays held for as long as the mapped guard is alive.
+pub struct MappedGuard<'a, U: ?Sized> {
+    data: NonNull<U>,
+    _guard: Box<dyn Guard + 'a>,
+    _marker: PhantomData<&'a mut U>,
+}
+
+/// Guard giving shared access to a part of the locked data.
+///
+/// Like `MappedGuard`, it keeps the original guard until it is dropped.
+pub struct MappedReadGuard<'a, U: ?Sized> {
+    data: NonNull<U>,
+    _guard: Box<dyn Guard + 'a>,
+    _marker: PhantomData<&'a U>,
+}
+
+// `guard` must deref to data owned by the lock rather than by the guard
+// itself, so that `data` stays valid when the guard is moved into its box.
+// This holds for the guards of the standard library locks.
+
+impl<'a, U: ?Sized> MappedGuard<'a, U> {
+    pub(crate) fn new<G>(
+        mut guard: G,
+        f: impl FnOnce(&mut G::Target) -> &mut U,
+    ) -> Self
+    where
+        G: DerefMut + 'a,
+    {
+        let data = NonNull::from(f(&mut *guard));
+
+        Self {
+            data,
+            _guard: Box::new(guard),
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn try_new<G>(
+        mut guard: G,
+        f: impl FnOnce(&mut G::Target) -> Option<&mut U>,
+    ) -> Result<Self, G>
+    where
+        G: DerefMut + 'a,
+    {
+        match f(&mut *guard).map(NonNull::from) {
+            Some(data) => Ok(Self {
+                data,
+                _guard: Box::new(guard),
+                _marker: PhantomData,
+            }),
+            None => Err(guard),
+        }
+    }
+}
+
+impl<'a, U: ?Sized> MappedReadGuard<'a, U> {
+    pub(crate) fn new<G>(guard: G, f: impl FnOnce(&G::Target) -> &U) -> Self
+    where
+        G: Deref + 'a,
+    {
+        let data = NonNull::from(f(&*guard));
+
+        Self {
+            data,
+            _guard: Box::new(guard),
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn try_new<G>(
+        guard: G,
+        f: impl FnOnce(&G::Target) -> Option<&U>,
+    ) -> Result<Self, G>
+    where
+        G: Deref + 'a,
+    {
+        match f(&*guard).map(NonNull::from) {
+            Some(data) => Ok(Self {
+                data,
+                _guard: Box::new(guard),
+                _marker: PhantomData,
+            }),
+            None => Err(guard),
+        }
+    }
+}
+
+impl<U: ?Sized> Deref for MappedGuard<'_, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the lock is held until `_guard` is dropped.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<U: ?Sized> DerefMut for MappedGuard<'_, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        // SAFETY: the lock is held until `_guard` is dropped.
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<U: ?Sized> Deref for MappedReadGuard<'_, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the lock is held until `_guard` is dropped.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+// SAFETY: shared references to the guards only give out shared references
+// to the data. Like the guards they wrap, they are never `Send`.
+unsafe impl<U: ?Sized + Sync> Sync for MappedGuard<'_, U> {}
+unsafe impl<U: ?Sized + Sync> Sync for MappedReadGuard<'_, U> {}
+
+impl<U: ?Sized + fmt::Debug> fmt::Debug for MappedGuard<'_, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<U: ?Sized + fmt::Debug> fmt::Debug for MappedReadGuard<'_, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,268 @@
+//! Standard library locks with different poison policies.
+//!
+//! The `Mutex` and `RwLock` implementations of `std::sync::Mutex` and
+//! `std::sync::RwLock` panic when the lock is poisoned. The types in this
+//! module wrap them to either recover the guard or hand the `LockResult` to
+//! the caller instead. `locktree!` uses them for locks with a `poison`
+//! attribute.
+
+use crate::{
+    MappableMutex, MappableRwLock, MappedGuard, MappedReadGuard, Mutex, New,
+    RwLock,
+};
+use std::sync::{LockResult, PoisonError, TryLockError};
+
+/// Locks that can be poisoned by a panicking holder.
+pub trait ClearPoison {
+    /// Whether a holder of the lock panicked.
+    fn is_poisoned(&self) -> bool;
+
+    /// Clear the poisoned state of the lock.
+    fn clear_poison(&self);
+}
+
+impl<T> ClearPoison for std::sync::Mutex<T> {
+    fn is_poisoned(&self) -> bool {
+        std::sync::Mutex::<T>::is_poisoned(self)
+    }
+
+    fn clear_poison(&self) {
+        std::sync::Mutex::<T>::clear_poison(self)
+    }
+}
+
+impl<T> ClearPoison for std::sync::RwLock<T> {
+    fn is_poisoned(&self) -> bool {
+        std::sync::RwLock::<T>::is_poisoned(self)
+    }
+
+    fn clear_poison(&self) {
+        std::sync::RwLock::<T>::clear_poison(self)
+    }
+}
+
+/// `std::sync::Mutex` which ignores poisoning.
+pub struct RecoverMutex<T>(std::sync::Mutex<T>);
+
+/// `std::sync::RwLock` which ignores poisoning.
+pub struct RecoverRwLock<T>(std::sync::RwLock<T>);
+
+/// `std::sync::Mutex` which returns a `LockResult` instead of a guard.
+pub struct PropagateMutex<T>(std::sync::Mutex<T>);
+
+/// `std::sync::RwLock` which returns a `LockResult` instead of a guard.
+pub struct PropagateRwLock<T>(std::sync::RwLock<T>);
+
+impl<T> Mutex for RecoverMutex<T> {
+    type Guard<'a>
+        = std::sync::MutexGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Self::Guard<'_> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        recover(self.0.try_lock())
+    }
+}
+
+impl<T> RwLock for RecoverRwLock<T> {
+    type ReadGuard<'a>
+        = std::sync::RwLockReadGuard<'a, T>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+        = std::sync::RwLockWriteGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Self::ReadGuard<'_> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> Self::WriteGuard<'_> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        recover(self.0.try_read())
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        recover(self.0.try_write())
+    }
+}
+
+impl<T> MappableMutex for RecoverMutex<T> {
+    type Target = T;
+    type MappedGuard<'a, U: ?Sized + 'a>
+        = MappedGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map<'a, U, F>(guard: Self::Guard<'a>, f: F) -> Self::MappedGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MappedGuard::new(guard, f)
+    }
+
+    fn try_map<'a, U, F>(
+        guard: Self::Guard<'a>,
+        f: F,
+    ) -> Result<Self::MappedGuard<'a, U>, Self::Guard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        MappedGuard::try_new(guard, f)
+    }
+}
+
+impl<T> MappableRwLock for RecoverRwLock<T> {
+    type Target = T;
+    type MappedReadGuard<'a, U: ?Sized + 'a>
+        = MappedReadGuard<'a, U>
+    where
+        Self: 'a;
+    type MappedWriteGuard<'a, U: ?Sized + 'a>
+        = MappedGuard<'a, U>
+    where
+        Self: 'a;
+
+    fn map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Self::MappedReadGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> &U,
+    {
+        MappedReadGuard::new(guard, f)
+    }
+
+    fn try_map_read<'a, U, F>(
+        guard: Self::ReadGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedReadGuard<'a, U>, Self::ReadGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        MappedReadGuard::try_new(guard, f)
+    }
+
+    fn map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Self::MappedWriteGuard<'a, U>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        MappedGuard::new(guard, f)
+    }
+
+    fn try_map_write<'a, U, F>(
+        guard: Self::WriteGuard<'a>,
+        f: F,
+    ) -> Result<Self::MappedWriteGuard<'a, U>, Self::WriteGuard<'a>>
+    where
+        Self: 'a,
+        U: ?Sized + 'a,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        MappedGuard::try_new(guard, f)
+    }
+}
+
+impl<T> Mutex for PropagateMutex<T> {
+    type Guard<'a>
+        = std::sync::LockResult<std::sync::MutexGuard<'a, T>>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Self::Guard<'_> {
+        self.0.lock()
+    }
+
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        propagate(self.0.try_lock())
+    }
+}
+
+impl<T> RwLock for PropagateRwLock<T> {
+    type ReadGuard<'a>
+        = std::sync::LockResult<std::sync::RwLockReadGuard<'a, T>>
+    where
+        Self: 'a;
+    type WriteGuard<'a>
+        = std::sync::LockResult<std::sync::RwLockWriteGuard<'a, T>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Self::ReadGuard<'_> {
+        self.0.read()
+    }
+
+    fn write(&self) -> Self::WriteGuard<'_> {
+        self.0.write()
+    }
+
+    fn try_read(&self) -> Option<Self::ReadGuard<'_>> {
+        propagate(self.0.try_read())
+    }
+
+    fn try_write(&self) -> Option<Self::WriteGuard<'_>> {
+        propagate(self.0.try_write())
+    }
+}
+
+macro_rules! impl_wrapper {
+    ($wrapper:ident, $lock:ident) => {
+        impl<T> New<T> for $wrapper<T> {
+            fn new(value: T) -> Self {
+                Self(std::sync::$lock::new(value))
+            }
+        }
+
+        impl<T> ClearPoison for $wrapper<T> {
+            fn is_poisoned(&self) -> bool {
+                self.0.is_poisoned()
+            }
+
+            fn clear_poison(&self) {
+                self.0.clear_poison()
+            }
+        }
+    };
+}
+
+impl_wrapper!(RecoverMutex, Mutex);
+impl_wrapper!(RecoverRwLock, RwLock);
+impl_wrapper!(PropagateMutex, Mutex);
+impl_wrapper!(PropagateRwLock, RwLock);
+
+fn recover<G>(result: Result<G, TryLockError<G>>) -> Option<G> {
+    match result {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::WouldBlock) => None,
+        Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+    }
+}
+
+fn propagate<G>(result: Result<G, TryLockError<G>>) -> Option<LockResult<G>> {
+    match result {
+        Ok(guard) => Some(Ok(guard)),
+        Err(TryLockError::WouldBlock) => None,
+        Err(TryLockError::Poisoned(error)) => Some(Err(error)),
+    }
+}
@@ -0,0 +1,55 @@
+use crate::TimedOut;
+#[cfg(any(feature = "async-lock", feature = "futures"))]
+use std::future::{poll_fn, Future};
+#[cfg(any(feature = "async-lock", feature = "futures"))]
+use std::pin::{pin, Pin};
+#[cfg(any(feature = "async-lock", feature = "futures"))]
+use std::task::Poll;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest sleep between two attempts of `poll_until`.
+const MAX_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Call `try_lock` until it succeeds or `deadline` is reached, backing off
+/// exponentially in between.
+pub(crate) fn poll_until<T>(
+    deadline: Instant,
+    mut try_lock: impl FnMut() -> Option<T>,
+) -> Result<T, TimedOut> {
+    let mut backoff = Duration::from_micros(1);
+    loop {
+        if let Some(guard) = try_lock() {
+            return Ok(guard);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(TimedOut);
+        }
+        thread::sleep(backoff.min(deadline - now));
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Wait for `lock` until `deadline` is reached, with a timer that doesn't
+/// depend on a particular executor.
+#[cfg(any(feature = "async-lock", feature = "futures"))]
+pub(crate) async fn timeout_at<G>(
+    deadline: Instant,
+    lock: impl Future<Output = G>,
+) -> Result<G, TimedOut> {
+    let mut lock = pin!(lock);
+    let mut delay = futures_timer::Delay::new(
+        deadline.saturating_duration_since(Instant::now()),
+    );
+    poll_fn(|cx| {
+        if let Poll::Ready(guard) = lock.as_mut().poll(cx) {
+            Poll::Ready(Ok(guard))
+        } else if Pin::new(&mut delay).poll(cx).is_ready() {
+            Poll::Ready(Err(TimedOut))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
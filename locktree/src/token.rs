@@ -0,0 +1,40 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+thread_local! {
+    static TAKEN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Per-thread token required to enter `exclusive` lock trees.
+///
+/// At most one `LockToken` exists per thread at any given time, and it cannot
+/// be sent to other threads. Entry accessors of `exclusive` trees borrow it
+/// mutably for as long as the returned guard and forward are alive, so a
+/// thread can't enter any `exclusive` tree while it is still inside one.
+pub struct LockToken {
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl LockToken {
+    /// Take the token for the current thread.
+    ///
+    /// Returns `None` if the token was already taken and has not been dropped
+    /// yet.
+    pub fn acquire() -> Option<Self> {
+        TAKEN.with(|taken| {
+            if taken.replace(true) {
+                None
+            } else {
+                Some(Self {
+                    _not_send: PhantomData,
+                })
+            }
+        })
+    }
+}
+
+impl Drop for LockToken {
+    fn drop(&mut self) {
+        TAKEN.with(|taken| taken.set(false));
+    }
+}
@@ -0,0 +1,36 @@
+use futures::lock::{MutexGuard, MutexLockFuture};
+use locktree::{backend, locktree};
+use std::time::Duration;
+
+struct UntimedLock<T>(futures::lock::Mutex<T>);
+
+impl<T> UntimedLock<T> {
+    fn new(value: T) -> Self {
+        Self(futures::lock::Mutex::new(value))
+    }
+
+    fn lock(&self) -> MutexLockFuture<'_, T> {
+        self.0.lock()
+    }
+
+    fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.0.try_lock()
+    }
+}
+
+backend! {
+    impl<T> AsyncMutex for UntimedLock<T> {
+        type Guard<'a> = MutexGuard<'a, T>;
+    }
+}
+
+locktree! {
+    Main {
+        untimed: async Mutex(UntimedLock)<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(0);
+    let _ = locks.lock_untimed_for(Duration::from_secs(1));
+}
@@ -0,0 +1,13 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        rw_lock: RwLock<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(0);
+    let (rw_lock, mut forward) = locks.write_rw_lock();
+    forward.downgrade_rw_lock(rw_lock);
+}
@@ -0,0 +1,16 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        cache: UpgradableRwLock(parking_lot)<Option<u32>>,
+        mutex: Mutex(parking_lot)<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(None, 0);
+    let (cache, mut forward) = locks.upgradable_read_cache();
+    let (mutex, _) = forward.lock_mutex();
+    let _cache = forward.upgrade_cache(cache);
+    drop(mutex);
+}
@@ -0,0 +1,22 @@
+use locktree::backend;
+use std::sync::MutexGuard;
+
+struct NoTryLock<T>(std::sync::Mutex<T>);
+
+impl<T> NoTryLock<T> {
+    fn new(value: T) -> Self {
+        Self(std::sync::Mutex::new(value))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, T> {
+        self.0.lock().unwrap()
+    }
+}
+
+backend! {
+    impl<T> Mutex for NoTryLock<T> {
+        type Guard<'a> = MutexGuard<'a, T>;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        new: Mutex<()>,
+        mutex: Mutex(std::sync::Mutex Extra)<()>,
+        rw_lock: RwLock<()>,
+        rw_lock: RwLock<()>,
+        __: Mutex<()>,
+        map: Mutx<std::collections::HashMap<u8, u16>>,
+    }
+
+    Main {}
+}
+
+fn main() {}
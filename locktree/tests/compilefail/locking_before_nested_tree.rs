@@ -0,0 +1,20 @@
+use locktree::locktree;
+
+locktree! {
+    Storage {
+        files: Mutex<Vec<String>>,
+    }
+
+    Main {
+        config: RwLock<String>,
+        storage: Tree<StorageLockTree>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(String::new(), StorageLockTree::new(Vec::new()));
+    let mut forward_storage = locks.enter_storage();
+    let _files = forward_storage.storage().lock_files();
+    // Invalid
+    let _ = forward_storage.read_config();
+}
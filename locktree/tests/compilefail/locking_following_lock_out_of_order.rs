@@ -0,0 +1,14 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        first: Mutex<()>,
+        second: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), ());
+    let (_first, forward) = locks.lock_first();
+    let _ = forward.try_lock_second_out_of_order();
+}
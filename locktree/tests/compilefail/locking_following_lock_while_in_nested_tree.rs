@@ -0,0 +1,20 @@
+use locktree::locktree;
+
+locktree! {
+    Storage {
+        index: RwLock<Vec<usize>>,
+    }
+
+    Main {
+        storage: Tree<StorageLockTree>,
+        stats: Mutex<u64>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(StorageLockTree::new(Vec::new()), 0);
+    let mut forward_storage = locks.enter_storage();
+    let _index = forward_storage.storage().read_index();
+    // Invalid
+    let _stats = forward_storage.lock_stats();
+}
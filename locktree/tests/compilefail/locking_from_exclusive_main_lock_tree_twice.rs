@@ -0,0 +1,16 @@
+use locktree::{locktree, LockToken};
+
+locktree! {
+    exclusive Main {
+        m0: Mutex<()>,
+        m1: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), ());
+    let mut token = LockToken::acquire().unwrap();
+    let _a = locks.lock_m0(&mut token);
+    // Invalid
+    let _ = locks.lock_m1(&mut token);
+}
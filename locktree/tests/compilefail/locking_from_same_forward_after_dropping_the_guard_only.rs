@@ -10,7 +10,8 @@ locktree! {
 fn main() {
     let locks = MainLockTree::new((), ());
     let (_a, mut forward_a) = locks.lock_m0();
-    let _b = forward_a.lock_m1().1;
+    let b = forward_a.lock_m1().1;
     // Invalid
     let _ = forward_a.lock_m1();
+    drop(b);
 }
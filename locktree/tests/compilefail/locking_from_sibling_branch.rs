@@ -0,0 +1,19 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        root: Mutex<()> {
+            left: Mutex<()>,
+        } {
+            right: Mutex<()>,
+        },
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), ());
+    let (_root, mut forward_root) = locks.lock_root();
+    let (_left, mut forward_left) = forward_root.lock_left();
+    // Invalid
+    let _ = forward_left.lock_right();
+}
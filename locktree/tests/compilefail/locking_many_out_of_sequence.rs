@@ -0,0 +1,14 @@
+use locktree::{lock_many, locktree};
+
+locktree! {
+    Main {
+        mutex0: Mutex<()>,
+        mutex1: Mutex<()>,
+        mutex2: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), ());
+    let _ = lock_many!(locks.lock_mutex0(), lock_mutex2, lock_mutex1);
+}
@@ -0,0 +1,22 @@
+use locktree::locktree;
+
+locktree! {
+    Storage {
+        index: RwLock<Vec<usize>>,
+    }
+
+    Main {
+        config: RwLock<String>,
+        storage: Tree<StorageLockTree>,
+        stats: Mutex<u64>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(String::new(), StorageLockTree::new(Vec::new()), 0);
+    let (_config, mut forward_config) = locks.read_config();
+    let mut forward_storage = forward_config.enter_storage();
+    let _stats = forward_storage.lock_stats();
+    // Invalid
+    let _index = forward_storage.storage().read_index();
+}
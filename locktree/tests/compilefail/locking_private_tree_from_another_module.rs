@@ -0,0 +1,15 @@
+mod locks {
+    use locktree::locktree;
+
+    locktree! {
+        Main {
+            mutex: Mutex<()>,
+        }
+    }
+}
+
+fn main() {
+    // Invalid
+    let locks = locks::MainLockTree::new(());
+    let _ = locks.lock_mutex();
+}
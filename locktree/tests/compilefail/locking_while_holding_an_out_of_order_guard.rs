@@ -0,0 +1,17 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        first: Mutex<()>,
+        second: Mutex<()>,
+        third: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), ());
+    let (_second, mut forward) = locks.lock_second();
+    let first = forward.try_lock_first_out_of_order().unwrap();
+    let _ = forward.lock_third();
+    drop(first);
+}
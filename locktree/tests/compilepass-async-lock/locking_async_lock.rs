@@ -0,0 +1,32 @@
+use futures::executor::block_on;
+use locktree::locktree;
+use std::time::Duration;
+
+locktree! {
+    Main {
+        mutex: async Mutex(async_lock)<u32>,
+        rw_lock: async RwLock(async_lock)<u32>,
+    }
+}
+
+fn main() {
+    block_on(async {
+        let locks = MainLockTree::new(0, 0);
+        {
+            let (mutex, mut forward) = locks.lock_mutex();
+            let mut mutex = mutex.await;
+            *mutex += 1;
+            assert!(locks.try_lock_mutex().is_none());
+            let (rw_lock, _) = forward.write_rw_lock();
+            *rw_lock.await += 1;
+        }
+        assert_eq!(*locks.lock_mutex().0.await, 1);
+        let (rw_lock, _) = locks.read_rw_lock();
+        let rw_lock = rw_lock.await;
+        assert_eq!(*rw_lock, 1);
+        assert!(locks.try_read_rw_lock().is_some());
+        assert!(locks.try_write_rw_lock().is_none());
+        let timeout = Duration::from_millis(10);
+        assert!(locks.write_rw_lock_for(timeout).0.await.is_err());
+    });
+}
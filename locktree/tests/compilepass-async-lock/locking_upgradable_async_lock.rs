@@ -0,0 +1,32 @@
+use futures::executor::block_on;
+use locktree::locktree;
+
+locktree! {
+    Main {
+        cache: async UpgradableRwLock(async_lock)<Option<u32>>,
+        mutex: async Mutex(async_lock)<u32>,
+    }
+}
+
+fn main() {
+    block_on(async {
+        let locks = MainLockTree::new(None, 0);
+        {
+            let (cache, mut forward) = locks.upgradable_read_cache();
+            let cache = cache.await;
+            assert!(locks.try_read_cache().is_some());
+            assert!(locks.try_upgradable_read_cache().is_none());
+            if cache.is_none() {
+                let mut cache = forward.upgrade_cache(cache).await;
+                assert!(locks.try_read_cache().is_none());
+                *cache = Some(1);
+            }
+            let (mutex, _) = forward.lock_mutex();
+            *mutex.await += 1;
+        }
+
+        let (cache, _) = locks.try_upgradable_read_cache().unwrap();
+        assert_eq!(*cache, Some(1));
+        assert_eq!(*locks.lock_mutex().0.await, 1);
+    });
+}
@@ -0,0 +1,39 @@
+use async_std::sync::{Mutex, RwLock};
+use futures::executor::block_on;
+use locktree::locktree;
+use std::time::Duration;
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<u32>,
+        rw_lock: async RwLock(RwLock)<u32>,
+        shorthand_mutex: async Mutex(async_std)<u32>,
+        shorthand_rw_lock: async UpgradableRwLock(async_std)<u32>,
+    }
+}
+
+fn main() {
+    block_on(async {
+        let locks = MainLockTree::new(0, 0, 0, 0);
+        {
+            let (mutex, mut forward) = locks.lock_mutex();
+            let mut mutex = mutex.await;
+            *mutex += 1;
+            assert!(locks.try_lock_mutex().is_none());
+            let (rw_lock, mut forward) = forward.write_rw_lock();
+            let rw_lock = forward.downgrade_rw_lock(rw_lock.await);
+            let (shorthand_mutex, mut forward) = forward.lock_shorthand_mutex();
+            *shorthand_mutex.await += *mutex + *rw_lock;
+            let (shorthand_rw_lock, mut forward) =
+                forward.upgradable_read_shorthand_rw_lock();
+            let shorthand_rw_lock = shorthand_rw_lock.await;
+            *forward.upgrade_shorthand_rw_lock(shorthand_rw_lock).await += 1;
+        }
+        assert_eq!(*locks.lock_shorthand_mutex().0.await, 1);
+        assert_eq!(*locks.read_shorthand_rw_lock().0.await, 1);
+        let (rw_lock, _) = locks.read_rw_lock();
+        let _rw_lock = rw_lock.await;
+        let timeout = Duration::from_millis(10);
+        assert!(locks.write_rw_lock_for(timeout).0.await.is_err());
+    });
+}
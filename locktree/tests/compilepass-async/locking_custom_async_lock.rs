@@ -0,0 +1,124 @@
+use futures::lock::{MutexGuard, MutexLockFuture};
+use locktree::{backend, locktree};
+use std::time::{Duration, Instant};
+
+/// Lock with `async fn`s.
+struct AsyncFnLock<T>(futures::lock::Mutex<T>);
+
+impl<T> AsyncFnLock<T> {
+    fn new(value: T) -> Self {
+        Self(futures::lock::Mutex::new(value))
+    }
+
+    async fn acquire(&self) -> MutexGuard<'_, T> {
+        self.0.lock().await
+    }
+
+    fn try_acquire(&self) -> Option<MutexGuard<'_, T>> {
+        self.0.try_lock()
+    }
+
+    async fn acquire_until(
+        &self,
+        deadline: Instant,
+    ) -> Option<MutexGuard<'_, T>> {
+        tokio::time::timeout_at(deadline.into(), self.0.lock())
+            .await
+            .ok()
+    }
+}
+
+backend! {
+    impl<T> AsyncMutex for AsyncFnLock<T> {
+        type Guard<'a> = MutexGuard<'a, T>;
+
+        fn lock = acquire;
+        fn try_lock = try_acquire;
+        fn lock_until = acquire_until;
+    }
+}
+
+/// Lock returning named futures.
+struct NamedLock<T>(futures::lock::Mutex<T>);
+
+impl<T> NamedLock<T> {
+    fn from_value(value: T) -> Self {
+        Self(futures::lock::Mutex::new(value))
+    }
+
+    fn read(&self) -> MutexLockFuture<'_, T> {
+        self.0.lock()
+    }
+
+    fn try_read(&self) -> Result<MutexGuard<'_, T>, ()> {
+        self.0.try_lock().ok_or(())
+    }
+
+    fn read_until(
+        &self,
+        deadline: Instant,
+    ) -> tokio::time::Timeout<MutexLockFuture<'_, T>> {
+        tokio::time::timeout_at(deadline.into(), self.0.lock())
+    }
+}
+
+backend! {
+    impl<T> AsyncRwLock for NamedLock<T> {
+        type ReadGuard<'a> = MutexGuard<'a, T>;
+        type WriteGuard<'a> = MutexGuard<'a, T>;
+
+        fn new = from_value;
+        fn write = read;
+        fn try_write = try_read;
+        fn write_until = read_until;
+        fn downgrade = std::convert::identity;
+    }
+}
+
+/// Lock without timed locking.
+struct UntimedLock<T>(futures::lock::Mutex<T>);
+
+impl<T> UntimedLock<T> {
+    fn new(value: T) -> Self {
+        Self(futures::lock::Mutex::new(value))
+    }
+
+    fn lock(&self) -> MutexLockFuture<'_, T> {
+        self.0.lock()
+    }
+
+    fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.0.try_lock()
+    }
+}
+
+backend! {
+    impl<T> AsyncMutex for UntimedLock<T> {
+        type Guard<'a> = MutexGuard<'a, T>;
+    }
+}
+
+locktree! {
+    Main {
+        async_fn: async Mutex(AsyncFnLock)<u32>,
+        named: async RwLock(NamedLock)<u32>,
+        untimed: async Mutex(UntimedLock)<u32>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let locks = MainLockTree::new(0, 0, 0);
+    let (async_fn, mut forward) = locks.lock_async_fn();
+    let mut async_fn = async_fn.await;
+    assert!(locks.try_lock_async_fn().is_none());
+    assert!(locks.lock_async_fn_for(Duration::ZERO).0.await.is_err());
+    let (named, mut forward) = forward.write_named_for(Duration::from_secs(1));
+    let mut named = named.await.unwrap();
+    assert!(locks.try_read_named().is_none());
+    let (untimed, _) = forward.lock_untimed();
+    let mut untimed = untimed.await;
+    *async_fn += 1;
+    *named += 1;
+    *untimed += 1;
+}
@@ -0,0 +1,28 @@
+use futures::executor::block_on;
+use locktree::locktree;
+use std::time::Duration;
+
+locktree! {
+    Main {
+        mutex0: async Mutex(futures)<u32>,
+        mutex1: async Mutex(futures)<u32>,
+    }
+}
+
+fn main() {
+    block_on(async {
+        let locks = MainLockTree::new(0, 0);
+        {
+            let (mutex0, mut forward) = locks.lock_mutex0();
+            let mut mutex0 = mutex0.await;
+            *mutex0 += 1;
+            assert!(locks.try_lock_mutex0().is_none());
+            let timeout = Duration::from_millis(10);
+            assert!(locks.lock_mutex0_for(timeout).0.await.is_err());
+            let (mutex1, _) = forward.lock_mutex1();
+            *mutex1.await += 1;
+        }
+        assert_eq!(*locks.lock_mutex0().0.await, 1);
+        assert_eq!(*locks.lock_mutex1().0.await, 1);
+    });
+}
@@ -0,0 +1,21 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        rw_lock: RwLock(parking_lot)<u32>,
+        mutex: Mutex<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(0, 0);
+    let (mut rw_lock, mut forward) = locks.write_rw_lock();
+    *rw_lock += 1;
+    let rw_lock = forward.downgrade_rw_lock(rw_lock);
+    assert!(locks.try_read_rw_lock().is_some());
+    assert!(locks.try_write_rw_lock().is_none());
+
+    let (mut mutex, _) = forward.lock_mutex();
+    *mutex += *rw_lock;
+    assert_eq!(*mutex, 1);
+}
@@ -0,0 +1,32 @@
+use locktree::locktree;
+use std::time::Duration;
+
+locktree! {
+    Main {
+        mutex: Mutex(parking_lot)<u32>,
+        fair_mutex: Mutex(parking_lot_fair)<u32>,
+        reentrant_mutex: Mutex(parking_lot_reentrant)<u32>,
+        rw_lock: RwLock(parking_lot)<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(0, 1, 2, 3);
+    let (mut mutex, mut forward) = locks.lock_mutex();
+    *mutex += 1;
+    assert!(locks.try_lock_mutex().is_none());
+    let (mut fair_mutex, mut forward) = forward.lock_fair_mutex();
+    *fair_mutex += 1;
+    let (reentrant_mutex, mut forward) = forward.lock_reentrant_mutex();
+    assert_eq!(*reentrant_mutex, 2);
+    let (mut rw_lock, _) = forward.write_rw_lock();
+    *rw_lock += 1;
+    assert!(locks
+        .read_rw_lock_for(Duration::from_millis(10))
+        .is_err());
+    drop((mutex, fair_mutex, reentrant_mutex, rw_lock));
+
+    assert_eq!(*locks.lock_mutex().0, 1);
+    assert_eq!(*locks.lock_fair_mutex().0, 2);
+    assert_eq!(*locks.read_rw_lock().0, 4);
+}
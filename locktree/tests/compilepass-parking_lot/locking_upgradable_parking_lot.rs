@@ -0,0 +1,28 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        cache: UpgradableRwLock(parking_lot)<Option<u32>>,
+        mutex: Mutex(parking_lot)<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(None, 0);
+    {
+        let (cache, mut forward) = locks.upgradable_read_cache();
+        assert!(locks.try_read_cache().is_some());
+        assert!(locks.try_upgradable_read_cache().is_none());
+        if cache.is_none() {
+            let mut cache = forward.upgrade_cache(cache);
+            assert!(locks.try_read_cache().is_none());
+            *cache = Some(1);
+        }
+        let (mut mutex, _) = forward.lock_mutex();
+        *mutex += 1;
+    }
+
+    let (cache, _) = locks.try_upgradable_read_cache().unwrap();
+    assert_eq!(*cache, Some(1));
+    assert_eq!(*locks.lock_mutex().0, 1);
+}
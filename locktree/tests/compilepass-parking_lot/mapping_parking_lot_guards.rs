@@ -0,0 +1,25 @@
+use locktree::{locktree, parking_lot};
+
+locktree! {
+    Main {
+        mutex: Mutex(parking_lot)<(u32, u32)>,
+        rw_lock: RwLock(parking_lot)<Vec<u32>>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((0, 0), vec![1, 2]);
+    let (mutex, mut forward) = locks.lock_mutex();
+    let mut second: parking_lot::MappedMutexGuard<'_, u32> =
+        forward.map_mutex(mutex, |x| &mut x.1);
+    *second += 1;
+    let (rw_lock, forward_rw_lock) = forward.write_rw_lock();
+    let mut slice =
+        forward_rw_lock.map_write_rw_lock(rw_lock, |x| x.as_mut_slice());
+    slice[0] = *second;
+    drop((second, slice));
+
+    let (rw_lock, _) = locks.read_rw_lock();
+    let third = locks.try_map_read_rw_lock(rw_lock, |x| x.get(2));
+    assert_eq!(*third.err().unwrap(), [1, 2]);
+}
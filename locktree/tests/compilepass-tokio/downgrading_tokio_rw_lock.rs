@@ -0,0 +1,24 @@
+use locktree::locktree;
+use tokio::sync::{Mutex, RwLock};
+
+locktree! {
+    Main {
+        rw_lock: async RwLock(RwLock)<u32>,
+        mutex: async Mutex(Mutex)<u32>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let locks = MainLockTree::new(0, 0);
+    let (rw_lock, mut forward) = locks.write_rw_lock();
+    let mut rw_lock = rw_lock.await;
+    *rw_lock += 1;
+    let rw_lock = forward.downgrade_rw_lock(rw_lock);
+    assert_eq!(*locks.read_rw_lock().0.await, 1);
+    assert!(locks.try_write_rw_lock().is_none());
+    let (mutex, _) = forward.lock_mutex();
+    *mutex.await += *rw_lock;
+    drop(rw_lock);
+    assert!(locks.try_write_rw_lock().is_some());
+}
@@ -0,0 +1,33 @@
+use locktree::locktree;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<u32>,
+        rw_lock: async RwLock(RwLock)<u32>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let locks = Arc::new(MainLockTree::new(0, 0));
+    let task = tokio::spawn({
+        let locks = locks.clone();
+        async move {
+            let (mutex, mut forward) = locks.lock_mutex();
+            let mut mutex = mutex.await;
+            tokio::task::yield_now().await;
+            let (rw_lock, _) =
+                forward.write_rw_lock_for(Duration::from_secs(1));
+            let mut rw_lock = rw_lock.await.unwrap();
+            tokio::task::yield_now().await;
+            *mutex += 1;
+            *rw_lock += 1;
+        }
+    });
+    task.await.unwrap();
+    assert_eq!(*locks.lock_mutex().0.await, 1);
+    assert_eq!(*locks.read_rw_lock().0.await, 1);
+}
@@ -0,0 +1,34 @@
+use locktree::locktree;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::LocalSet;
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<Rc<u32>>,
+        rw_lock: async RwLock(RwLock)<Rc<u32>>,
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let locks = Rc::new(MainLockTree::new(Rc::new(0), Rc::new(0)));
+    let local = LocalSet::new();
+    local.spawn_local({
+        let locks = locks.clone();
+        async move {
+            let deadline = Instant::now() + Duration::from_secs(1);
+            let (mutex, mut forward) = locks.lock_mutex_with_deadline(deadline);
+            let mut mutex = mutex.await.unwrap();
+            tokio::task::yield_now().await;
+            let (rw_lock, _) = forward.write_rw_lock();
+            let mut rw_lock = rw_lock.await.unwrap();
+            *mutex = Rc::new(**mutex + 1);
+            *rw_lock = Rc::new(**rw_lock + 1);
+        }
+    });
+    local.await;
+    assert_eq!(**locks.lock_mutex().0.await, 1);
+    assert_eq!(**locks.read_rw_lock().0.await, 1);
+}
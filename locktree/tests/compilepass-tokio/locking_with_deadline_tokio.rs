@@ -0,0 +1,35 @@
+use locktree::locktree;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Barrier, Mutex, RwLock};
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<()>,
+        rw_lock: async RwLock(RwLock)<()>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let locks = Arc::new(MainLockTree::new((), ()));
+    let barrier = Arc::new(Barrier::new(2));
+    let holder = tokio::spawn({
+        let locks = locks.clone();
+        let barrier = barrier.clone();
+        async move {
+            let _rw_lock = locks.write_rw_lock().0.await;
+            barrier.wait().await;
+            barrier.wait().await;
+        }
+    });
+    barrier.wait().await;
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    let (mutex, mut forward) = locks.lock_mutex_with_deadline(deadline);
+    let _mutex = mutex.await.unwrap();
+    let (rw_lock, _) = forward.read_rw_lock();
+    assert_eq!(rw_lock.await.err().unwrap().lock(), "rw_lock");
+    barrier.wait().await;
+    holder.await.unwrap();
+}
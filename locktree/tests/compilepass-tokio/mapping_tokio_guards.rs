@@ -0,0 +1,27 @@
+use locktree::locktree;
+use tokio::sync::{MappedMutexGuard, Mutex, RwLock};
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<(u32, u32)>,
+        rw_lock: async RwLock(RwLock)<Vec<u32>>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let locks = MainLockTree::new((0, 0), vec![1, 2]);
+    let (mutex, mut forward) = locks.lock_mutex();
+    let mut second: MappedMutexGuard<'_, u32> =
+        forward.map_mutex(mutex.await, |x| &mut x.1);
+    *second += 1;
+    let (rw_lock, forward_rw_lock) = forward.write_rw_lock();
+    let mut slice =
+        forward_rw_lock.map_write_rw_lock(rw_lock.await, |x| x.as_mut_slice());
+    slice[0] = *second;
+    drop((second, slice));
+
+    let (rw_lock, _) = locks.read_rw_lock();
+    let first = locks.try_map_read_rw_lock(rw_lock.await, |x| x.first());
+    assert_eq!(*first.ok().unwrap(), 1);
+}
@@ -0,0 +1,22 @@
+use locktree::{locktree, TimedOut};
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<()>,
+        rw_lock: async RwLock(RwLock)<()>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let timeout = Duration::from_millis(10);
+    let locks = MainLockTree::new((), ());
+    let (mutex, mut forward) = locks.lock_mutex_for(timeout);
+    let _mutex = mutex.await.unwrap();
+    assert_eq!(locks.lock_mutex_for(timeout).0.await.err(), Some(TimedOut));
+    let (rw_lock, _) = forward.write_rw_lock_for(timeout);
+    let _rw_lock = rw_lock.await.unwrap();
+    assert!(locks.read_rw_lock_for(timeout).0.await.is_err());
+}
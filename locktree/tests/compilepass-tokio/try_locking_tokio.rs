@@ -0,0 +1,18 @@
+use locktree::locktree;
+use tokio::sync::{Mutex, RwLock};
+
+locktree! {
+    Main {
+        mutex: async Mutex(Mutex)<()>,
+        rw_lock: async RwLock(RwLock)<()>,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let locks = MainLockTree::new((), ());
+    let (_mutex, mut forward) = locks.try_lock_mutex().unwrap();
+    assert!(locks.try_lock_mutex().is_none());
+    let (_rw_lock, _) = forward.try_write_rw_lock().unwrap();
+    assert!(locks.try_read_rw_lock().is_none());
+}
@@ -0,0 +1,62 @@
+use locktree::locktree;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq)]
+struct Config {
+    capacity: usize,
+}
+
+locktree! {
+    #[builder]
+    Main {
+        config: Shared<Config> = (Config { capacity: 16 }),
+        queue: Mutex<Vec<usize>> = Vec::new() {
+            pending: Mutex<usize> = 0,
+        } {
+            done: RwLock<usize> = 0,
+        },
+        cache: RwLock<HashMap<String, usize>> = HashMap::new(),
+    }
+
+    #[builder]
+    Partial {
+        name: Mutex<String>,
+        count: Mutex<u64> = 1,
+    }
+
+    Plain {
+        build: Mutex<u32>,
+        builder: Mutex<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::default();
+    assert_eq!(locks.config().capacity, 16);
+    assert!(locks.lock_queue().0.is_empty());
+    assert!(locks.read_cache().0.is_empty());
+
+    let locks = MainLockTree::builder()
+        .cache(HashMap::new())
+        .done(2)
+        .config(Config { capacity: 4 })
+        .build()
+        .unwrap();
+    assert_eq!(*locks.config(), Config { capacity: 4 });
+    assert_eq!(*locks.read_done().0, 2);
+    assert_eq!(*locks.lock_pending().0, 0);
+
+    let locks = PartialLockTree::builder()
+        .name("partial".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(*locks.lock_name().0, "partial");
+    assert_eq!(*locks.lock_count().0, 1);
+
+    let error = PartialLockTree::builder().count(2).build().err().unwrap();
+    assert_eq!(error.lock(), "name");
+
+    let locks = PlainLockTree::new(1, 2);
+    let (build, mut forward) = locks.lock_build();
+    assert_eq!(*build + *forward.lock_builder().0, 3);
+}
@@ -0,0 +1,35 @@
+//! Generated items of public trees are documented.
+
+#![deny(missing_docs)]
+
+/// Locks of the application.
+pub mod locks {
+    use locktree::locktree;
+
+    locktree! {
+        /// Locks of the storage.
+        pub Storage {
+            index: RwLock<Vec<usize>>,
+        }
+
+        /// Locks of the application.
+        #[builder]
+        #[poison(recover)]
+        pub Main {
+            /// Name of the application.
+            name: Shared<String> = String::new(),
+            config: RwLock<String> = String::new(),
+            storage: Tree<StorageLockTree>,
+            stats: Mutex<u64> = 0,
+        }
+    }
+}
+
+fn main() {
+    let locks = locks::MainLockTree::builder()
+        .storage(locks::StorageLockTree::new(Vec::new()))
+        .build()
+        .unwrap();
+    let (_config, mut forward) = locks.read_config();
+    let _ = forward.enter_storage().into_lock_stats();
+}
@@ -0,0 +1,35 @@
+use locktree::locktree;
+use std::thread;
+use std::time::Duration;
+
+struct Buffer(Vec<u8>);
+
+locktree! {
+    #[poison(recover)]
+    Main<'buf> {
+        buffer: Mutex<&'buf mut Buffer>,
+        stats: RwLock<&'buf mut usize>,
+    }
+}
+
+fn main() {
+    let mut buffer = Buffer(Vec::new());
+    let mut stats = 0;
+    let locks = MainLockTree::new(&mut buffer, &mut stats);
+    thread::scope(|scope| {
+        for i in 0..4 {
+            let locks = &locks;
+            scope.spawn(move || {
+                let (mut buffer, mut forward) = locks.lock_buffer();
+                buffer.0.push(i);
+                let mut stats =
+                    forward.write_stats_for(Duration::from_secs(1)).unwrap().0;
+                **stats += 1;
+            });
+        }
+    });
+    assert!(locks.try_read_stats().is_some());
+    drop(locks);
+    assert_eq!(buffer.0.len(), 4);
+    assert_eq!(stats, 4);
+}
@@ -0,0 +1,21 @@
+use locktree::{locktree, LockToken};
+
+locktree! {
+    exclusive Main {
+        m0: Mutex<()>,
+        m1: Mutex<()>,
+        m2: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), ());
+    let mut token = LockToken::acquire().unwrap();
+    assert!(LockToken::acquire().is_none());
+    {
+        let (_a, mut forward_a) = locks.lock_m0(&mut token);
+        let (_b, mut forward_b) = forward_a.lock_m1();
+        let _ = forward_b.lock_m2();
+    }
+    let _ = locks.lock_m1(&mut token);
+}
@@ -1,4 +1,4 @@
-use locktree::{locktree, New, Mutex, plug::PlugLifetime, PluggedGuard};
+use locktree::{backend, locktree};
 use std::marker::PhantomData;
 
 locktree! {
@@ -11,26 +11,28 @@ locktree! {
 
 struct DummyLock<T>(PhantomData<T>);
 
-impl<T> New<T> for DummyLock<T> {
+impl<T> DummyLock<T> {
     fn new(_: T) -> Self {
         Self(PhantomData)
     }
-}
-
-impl<T> Mutex for DummyLock<T> {
-    type Guard = DummyGuard;
 
-    fn lock(&self) -> PluggedGuard<Self::Guard> {
+    fn lock(&self) -> DummyGuard {
         DummyGuard
     }
-}
 
-struct DummyGuard;
+    fn try_lock(&self) -> Option<DummyGuard> {
+        Some(DummyGuard)
+    }
+}
 
-impl<'a> PlugLifetime<'a> for DummyGuard {
-    type Type = DummyGuard;
+backend! {
+    impl<T> Mutex for DummyLock<T> {
+        type Guard<'a> = DummyGuard;
+    }
 }
 
+struct DummyGuard;
+
 fn main() {
     let locks = MainLockTree::new(());
     let _a = locks.lock_mutex();
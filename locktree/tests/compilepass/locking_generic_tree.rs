@@ -0,0 +1,27 @@
+use locktree::locktree;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+locktree! {
+    Cache<K, V> where K: Eq + Hash + 'static, V: 'static {
+        index: RwLock<HashMap<K, V>>,
+        hits: Mutex<u64>,
+    }
+}
+
+fn get<K, V>(cache: &CacheLockTree<K, V>, key: &K) -> Option<V>
+where
+    K: Eq + Hash + 'static,
+    V: Clone + 'static,
+{
+    let (index, mut forward) = cache.read_index();
+    let (mut hits, _) = forward.lock_hits();
+    *hits += 1;
+
+    index.get(key).cloned()
+}
+
+fn main() {
+    let cache = CacheLockTree::new(HashMap::from([(1, "one")]), 0);
+    assert_eq!(get(&cache, &1), Some("one"));
+}
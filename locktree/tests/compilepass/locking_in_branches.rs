@@ -0,0 +1,30 @@
+use locktree::locktree;
+
+locktree! {
+    Main {
+        root: Mutex<()> {
+            left0: Mutex<()>,
+            left1: Mutex<()>,
+        } {
+            right: Mutex<()>,
+        },
+        last: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), (), (), ());
+    {
+        let (_root, mut forward_root) = locks.lock_root();
+        let (_left0, mut forward_left0) = forward_root.lock_left0();
+        let (_left1, mut forward_left1) = forward_left0.lock_left1();
+        let _ = forward_left1.lock_last();
+    }
+    {
+        let (_root, mut forward_root) = locks.lock_root();
+        let (_right, mut forward_right) = forward_root.lock_right();
+        let _ = forward_right.lock_last();
+    }
+    let (_left1, mut forward_left1) = locks.lock_left1();
+    let _ = forward_left1.lock_last();
+}
@@ -0,0 +1,33 @@
+use locktree::{lock_many, locktree, LockToken};
+
+locktree! {
+    Main {
+        mutex0: Mutex<()>,
+        rw_lock: RwLock<()>,
+        mutex1: Mutex<()>,
+        mutex2: Mutex<()>,
+    }
+
+    exclusive Exclusive {
+        mutex0: Mutex<()>,
+        mutex1: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), (), ());
+    {
+        let ((_mutex0, _rw_lock, _mutex2), _) =
+            lock_many!(locks.lock_mutex0(), read_rw_lock, lock_mutex2);
+    }
+    {
+        let (_rw_lock, mut forward) = locks.write_rw_lock();
+        let ((_mutex1, _mutex2), _) =
+            lock_many!(forward.lock_mutex1(), lock_mutex2,);
+    }
+
+    let locks = ExclusiveLockTree::new((), ());
+    let mut token = LockToken::acquire().unwrap();
+    let ((_mutex0, _mutex1), _) =
+        lock_many!(locks.lock_mutex0(&mut token), lock_mutex1);
+}
@@ -0,0 +1,31 @@
+use locktree::locktree;
+
+locktree! {
+    Storage {
+        index: RwLock<Vec<usize>>,
+        files: Mutex<Vec<String>>,
+    }
+
+    Main {
+        config: RwLock<String>,
+        storage: Tree<StorageLockTree>,
+        stats: Mutex<u64>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(
+        String::new(),
+        StorageLockTree::new(Vec::new(), Vec::new()),
+        0,
+    );
+    let (_config, mut forward_config) = locks.read_config();
+    let mut forward_storage = forward_config.enter_storage();
+    {
+        let storage = forward_storage.storage();
+        let (_index, mut forward_index) = storage.read_index();
+        let _files = forward_index.lock_files();
+    }
+    let (mut stats, _) = forward_storage.lock_stats();
+    *stats += 1;
+}
@@ -0,0 +1,38 @@
+use locktree::locktree;
+use std::panic::{self, AssertUnwindSafe};
+
+locktree! {
+    #[poison(recover)]
+    Main {
+        mutex: Mutex<u32>,
+        #[poison(propagate)]
+        rw_lock: RwLock<u32>,
+        #[poison(panic)]
+        other: Mutex<u32>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(0, 0, 0);
+    panic::set_hook(Box::new(|_| {}));
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        let (mut mutex, mut forward) = locks.lock_mutex();
+        let mut rw_lock = forward.write_rw_lock().0.unwrap();
+        *mutex = 1;
+        *rw_lock = 1;
+        panic!();
+    }));
+
+    let (mutex, mut forward) = locks.lock_mutex();
+    assert_eq!(*mutex, 1);
+    let rw_lock = forward.read_rw_lock().0.unwrap_err().into_inner();
+    assert_eq!(*rw_lock, 1);
+    drop(rw_lock);
+    drop(forward);
+    drop(mutex);
+
+    locks.clear_poison_mutex();
+    locks.clear_poison_rw_lock();
+    assert!(locks.read_rw_lock().0.is_ok());
+    assert_eq!(*locks.lock_other().0, 0);
+}
@@ -0,0 +1,19 @@
+mod locks {
+    use locktree::locktree;
+
+    locktree! {
+        pub Main {
+            m0: Mutex<()>,
+            m1: Mutex<()>,
+        }
+    }
+}
+
+fn lock_in_sequence(locks: &locks::MainLockTree) {
+    let (_a, mut forward_a): (_, locks::MainLockTreeM0) = locks.lock_m0();
+    let _ = forward_a.lock_m1();
+}
+
+fn main() {
+    lock_in_sequence(&locks::MainLockTree::new((), ()));
+}
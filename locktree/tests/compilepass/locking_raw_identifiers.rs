@@ -0,0 +1,16 @@
+use locktree::locktree;
+
+locktree! {
+    r#Main {
+        r#type: Mutex<u32>,
+        r#match: RwLock<String>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(1, String::new());
+    let (mut ty, mut forward) = locks.lock_type();
+    let (mut name, _) = forward.write_match();
+    *ty += 1;
+    name.push_str("match");
+}
@@ -0,0 +1,32 @@
+use locktree::locktree;
+
+locktree! {
+    /// Locks in `m0`, `m1`, `m2` order.
+    #[derive(Debug)]
+    pub Main {
+        /// First lock.
+        m0: Mutex<()>,
+        /// Disabled lock.
+        #[cfg(any())]
+        m1: Mutex<()>,
+        /// Enabled alternative to the disabled lock.
+        #[cfg(not(any()))]
+        m1: Mutex<u8>,
+        /// Last lock.
+        m2: Mutex<()>,
+    }
+
+    #[cfg(any())]
+    Disabled {
+        m0: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), 1, ());
+    assert!(format!("{:?}", locks).starts_with("MainLockTree"));
+    let (_a, mut forward_a) = locks.lock_m0();
+    let (m1, mut forward_m1) = forward_a.lock_m1();
+    assert_eq!(*m1, 1);
+    let _ = forward_m1.lock_m2();
+}
@@ -0,0 +1,60 @@
+use locktree::{locktree, WithDeadline};
+use std::sync::Barrier;
+use std::thread;
+use std::time::{Duration, Instant};
+
+locktree! {
+    Storage {
+        index: RwLock<()>,
+        files: Mutex<()>,
+    }
+
+    Main {
+        first: Mutex<()>,
+        second: RwLock<()>,
+        storage: Tree<StorageLockTree>,
+        third: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks =
+        MainLockTree::new((), (), StorageLockTree::new((), ()), ());
+    let deadline = Instant::now() + Duration::from_millis(10);
+    {
+        let (_first, mut forward) =
+            locks.lock_first_with_deadline(deadline).unwrap();
+        let (_second, mut forward) = forward.read_second().unwrap();
+        let _ = forward.lock_third().unwrap();
+    }
+    {
+        let root = locks.with_deadline(deadline);
+        let (_first, mut forward) = root.lock_first().unwrap();
+        let _ = forward.try_write_second().unwrap();
+    }
+    let barrier = Barrier::new(2);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            let (_third, _) = locks.lock_third();
+            let (_files, _) = locks.storage.lock_files();
+            barrier.wait();
+            barrier.wait();
+        });
+        barrier.wait();
+
+        let deadline = Instant::now() + Duration::from_millis(10);
+        let (_first, mut forward) =
+            locks.lock_first_with_deadline(deadline).unwrap();
+        let (_second, mut forward) = forward.write_second().unwrap();
+        {
+            let mut forward = forward.enter_storage();
+            let storage = forward.storage();
+            let (_index, mut forward) = storage.read_index().unwrap();
+            let error = forward.lock_files().err().unwrap();
+            assert_eq!(error.lock(), "files");
+        }
+        let error = forward.lock_third().err().unwrap();
+        assert_eq!(error.lock(), "third");
+        barrier.wait();
+    });
+}
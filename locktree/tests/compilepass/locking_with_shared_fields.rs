@@ -0,0 +1,47 @@
+use locktree::{locktree, LockToken};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Config {
+    capacity: usize,
+}
+
+locktree! {
+    exclusive Main {
+        config: Shared<Config>,
+        queue: Mutex<Vec<usize>> {
+            pending: Mutex<usize>,
+        } {
+            done: RwLock<usize>,
+        },
+        hits: Shared<AtomicUsize>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new(
+        Config { capacity: 2 },
+        Vec::new(),
+        0,
+        0,
+        AtomicUsize::new(0),
+    );
+    assert_eq!(locks.config().capacity, 2);
+    let mut token = LockToken::acquire().unwrap();
+    {
+        let (mut queue, mut forward_queue) = locks.lock_queue(&mut token);
+        let config = forward_queue.config();
+        queue.push(config.capacity);
+        {
+            let (mut pending, forward_pending) = forward_queue.lock_pending();
+            *pending += 1;
+            forward_pending.hits().fetch_add(1, Ordering::Relaxed);
+        }
+        {
+            let (mut done, forward_done) = forward_queue.write_done();
+            *done += 1;
+            forward_done.hits().fetch_add(1, Ordering::Relaxed);
+        }
+        assert_eq!(config.capacity, 2);
+    }
+    assert_eq!(locks.hits().load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,56 @@
+use locktree::{locktree, MappedGuard};
+
+struct User {
+    name: String,
+    friends: Vec<String>,
+}
+
+locktree! {
+    Main {
+        user: Mutex<User>,
+        #[poison(recover)]
+        users: RwLock<Vec<User>>,
+    }
+}
+
+fn user_name(locks: &MainLockTree) -> MappedGuard<'_, String> {
+    let (user, _) = locks.lock_user();
+    locks.map_user(user, |user| &mut user.name)
+}
+
+fn main() {
+    let user = User {
+        name: String::new(),
+        friends: Vec::new(),
+    };
+    let locks = MainLockTree::new(user, Vec::new());
+    let mut name = user_name(&locks);
+    name.push_str("Alice");
+    assert!(locks.try_lock_user().is_none());
+    drop(name);
+
+    let (user, _) = locks.lock_user();
+    let user = locks
+        .try_map_user(user, |user| user.friends.first_mut())
+        .err()
+        .unwrap();
+    assert_eq!(user.name, "Alice");
+    drop(user);
+
+    let (user, mut forward) = locks.lock_user();
+    let (users, forward_users) = forward.write_users();
+    let mut users = forward_users.map_write_users(users, |x| x);
+    users.push(User {
+        name: "Bob".to_string(),
+        friends: user.friends.clone(),
+    });
+    drop((user, users));
+
+    let (users, forward) = locks.read_users();
+    let name = forward
+        .try_map_read_users(users, |users| users.first().map(|user| &user.name))
+        .ok()
+        .unwrap();
+    assert_eq!(*name, "Bob");
+    assert!(locks.try_write_users().is_none());
+}
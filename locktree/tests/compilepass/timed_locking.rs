@@ -0,0 +1,30 @@
+use locktree::{locktree, LockToken, TimedOut};
+use std::time::{Duration, Instant};
+
+locktree! {
+    Main {
+        mutex: Mutex<()>,
+        rw_lock: RwLock<()>,
+    }
+
+    exclusive Exclusive {
+        mutex: Mutex<()>,
+    }
+}
+
+fn main() {
+    let timeout = Duration::from_millis(10);
+    let locks = MainLockTree::new((), ());
+    {
+        let (_mutex, mut forward) = locks.lock_mutex_for(timeout).unwrap();
+        assert_eq!(locks.lock_mutex_for(timeout).err(), Some(TimedOut));
+        let _rw_lock = forward.read_rw_lock_until(Instant::now()).unwrap();
+        assert!(locks.read_rw_lock_for(timeout).is_ok());
+        assert!(locks.write_rw_lock_until(Instant::now() + timeout).is_err());
+    }
+    assert!(locks.write_rw_lock_for(Duration::MAX).is_ok());
+
+    let locks = ExclusiveLockTree::new(());
+    let mut token = LockToken::acquire().unwrap();
+    let _ = locks.lock_mutex_for(&mut token, timeout).unwrap();
+}
@@ -0,0 +1,32 @@
+use locktree::{locktree, LockToken};
+
+locktree! {
+    Main {
+        mutex: Mutex<()>,
+        rw_lock: RwLock<()>,
+    }
+
+    exclusive Exclusive {
+        mutex0: Mutex<()>,
+        mutex1: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), ());
+    {
+        let (_mutex, mut forward) = locks.try_lock_mutex().unwrap();
+        assert!(locks.try_lock_mutex().is_none());
+        let _rw_lock = forward.try_read_rw_lock().unwrap();
+        assert!(locks.try_read_rw_lock().is_some());
+        assert!(locks.try_write_rw_lock().is_none());
+    }
+    assert!(locks.try_write_rw_lock().is_some());
+
+    let locks = ExclusiveLockTree::new((), ());
+    let mut token = LockToken::acquire().unwrap();
+    if let Some((_mutex0, mut forward)) = locks.try_lock_mutex0(&mut token) {
+        let _ = forward.try_lock_mutex1().unwrap();
+    }
+    let _ = locks.try_lock_mutex1(&mut token).unwrap();
+}
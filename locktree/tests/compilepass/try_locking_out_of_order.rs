@@ -0,0 +1,22 @@
+use locktree::{locktree, WouldBlock};
+
+locktree! {
+    Main {
+        first: Mutex<()>,
+        second: RwLock<()>,
+        third: Mutex<()>,
+    }
+}
+
+fn main() {
+    let locks = MainLockTree::new((), (), ());
+    let (_second, mut forward) = locks.write_second();
+    {
+        let _first = forward.try_lock_first_out_of_order().unwrap();
+        assert_eq!(
+            forward.try_read_second_out_of_order().err(),
+            Some(WouldBlock),
+        );
+    }
+    let _ = forward.lock_third();
+}
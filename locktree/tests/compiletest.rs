@@ -3,5 +3,28 @@ fn compiletest() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compilefail/*.rs");
     t.pass("tests/compilepass/*.rs");
+    #[cfg(feature = "async")]
+    t.pass("tests/compilepass-async/*.rs");
+    // The errors list the built-in implementations of the lock traits.
+    #[cfg(all(
+        feature = "async",
+        not(any(
+            feature = "tokio",
+            feature = "async-lock",
+            feature = "futures"
+        ))
+    ))]
+    t.compile_fail("tests/compilefail-async/*.rs");
+    #[cfg(feature = "tokio")]
     t.pass("tests/compilepass-tokio/*.rs");
+    #[cfg(feature = "async-lock")]
+    t.pass("tests/compilepass-async-lock/*.rs");
+    #[cfg(feature = "async-std")]
+    t.pass("tests/compilepass-async-std/*.rs");
+    #[cfg(feature = "futures")]
+    t.pass("tests/compilepass-futures/*.rs");
+    #[cfg(feature = "parking_lot")]
+    t.pass("tests/compilepass-parking_lot/*.rs");
+    #[cfg(feature = "parking_lot")]
+    t.compile_fail("tests/compilefail-parking_lot/*.rs");
 }